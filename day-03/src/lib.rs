@@ -0,0 +1,334 @@
+use nom::{branch::alt, bytes::complete::is_not, character::complete::digit1, multi::many1};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct Number {
+    pub row: usize,
+    pub start: usize,
+    /// Exclusive end column
+    pub end: usize,
+    pub value: u32,
+}
+
+impl Number {
+    /// How the cell at `row`, `col` touches this number, if at all.
+    pub fn touches(&self, row: usize, col: usize) -> Option<Adjacency> {
+        let beside = col + 1 == self.start || col == self.end;
+        let within = (self.start..self.end).contains(&col);
+        if row == self.row {
+            beside.then_some(Adjacency::Orthogonal)
+        } else if row + 1 == self.row || row == self.row + 1 {
+            if within {
+                Some(Adjacency::Orthogonal)
+            } else {
+                beside.then_some(Adjacency::Diagonal)
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct Symbol {
+    pub row: usize,
+    pub col: usize,
+    pub char: char,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Schematic {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    number_index: HashMap<(usize, usize), usize>,
+    symbol_index: HashMap<(usize, usize), usize>,
+}
+
+impl Schematic {
+    pub fn parse(input: &str) -> Self {
+        let mut schematic = Self::default();
+        input
+            .lines()
+            .enumerate()
+            .for_each(|(row, l)| schematic.parse_line(row, l));
+        schematic
+    }
+
+    fn parse_line(&mut self, row: usize, i: &str) {
+        if i.is_empty() {
+            return;
+        }
+        many1(alt((
+            is_not("0123456789"),
+            digit1::<&str, nom::error::Error<&str>>,
+        )))(i)
+        .expect("invalid input")
+        .1
+        .into_iter()
+        .fold(0, |start, cur| {
+            let end = start + cur.len();
+            if let Ok(value) = cur.parse::<u32>() {
+                let idx = self.numbers.len();
+                self.numbers.push(Number {
+                    row,
+                    start,
+                    end,
+                    value,
+                });
+                (start..end).for_each(|col| {
+                    self.number_index.insert((row, col), idx);
+                });
+            } else {
+                cur.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c != '.')
+                    .for_each(|(offset, c)| {
+                        let col = start + offset;
+                        self.symbol_index.insert((row, col), self.symbols.len());
+                        self.symbols.push(Symbol { row, col, char: c });
+                    });
+            }
+            end
+        });
+    }
+
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    pub fn number_at(&self, row: usize, col: usize) -> Option<&Number> {
+        self.number_index
+            .get(&(row, col))
+            .map(|i| &self.numbers[*i])
+    }
+
+    pub fn symbol_at(&self, row: usize, col: usize) -> Option<&Symbol> {
+        self.symbol_index
+            .get(&(row, col))
+            .map(|i| &self.symbols[*i])
+    }
+
+    /// Every distinct number touching the symbol, including diagonally.
+    /// Numbers are told apart by their span, so equal values are all kept.
+    pub fn numbers_adjacent_to(&self, symbol: &Symbol) -> Vec<&Number> {
+        self.numbers_adjacent_to_with(symbol, Adjacency::Any)
+    }
+
+    pub fn numbers_adjacent_to_with(&self, symbol: &Symbol, adjacency: Adjacency) -> Vec<&Number> {
+        let mut indices = neighbours(symbol.row, symbol.col, symbol.col + 1)
+            .filter_map(|pos| self.number_index.get(&pos).copied())
+            .collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+            .into_iter()
+            .map(|i| &self.numbers[i])
+            .filter(|n| adjacency.matches(n.touches(symbol.row, symbol.col)))
+            .collect()
+    }
+
+    pub fn symbols_adjacent_to(&self, number: &Number) -> Vec<&Symbol> {
+        self.symbols_adjacent_to_with(number, Adjacency::Any)
+    }
+
+    pub fn symbols_adjacent_to_with(&self, number: &Number, adjacency: Adjacency) -> Vec<&Symbol> {
+        neighbours(number.row, number.start, number.end)
+            .filter_map(|pos| self.symbol_index.get(&pos))
+            .map(|i| &self.symbols[*i])
+            .filter(|s| adjacency.matches(number.touches(s.row, s.col)))
+            .collect()
+    }
+
+    /// Aggregated adjacent numbers for every symbol satisfying the query.
+    pub fn query<'a, P>(
+        &'a self,
+        query: &'a Query<P>,
+    ) -> impl Iterator<Item = (&'a Symbol, u64)> + 'a
+    where
+        P: Fn(char) -> bool,
+    {
+        self.symbols
+            .iter()
+            .filter(|s| (query.symbols)(s.char))
+            .filter_map(|s| {
+                let nums = self.numbers_adjacent_to_with(s, query.adjacency);
+                query.count.matches(nums.len()).then(|| {
+                    (
+                        s,
+                        query.aggregation.apply(nums.into_iter().map(|n| n.value)),
+                    )
+                })
+            })
+    }
+
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> + '_ {
+        self.numbers
+            .iter()
+            .filter(|n| !self.symbols_adjacent_to(n).is_empty())
+    }
+
+    pub fn gear_ratios(&self) -> Vec<u64> {
+        let gears = Query::new(|c| c == '*')
+            .count(Count::Exactly(2))
+            .aggregation(Aggregation::Product);
+        self.query(&gears).map(|(_, ratio)| ratio).collect()
+    }
+}
+
+impl From<&str> for Schematic {
+    fn from(s: &str) -> Self {
+        Self::parse(s)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Default)]
+pub enum Adjacency {
+    #[default]
+    Any,
+    Orthogonal,
+    Diagonal,
+}
+
+impl Adjacency {
+    fn matches(&self, touch: Option<Adjacency>) -> bool {
+        match (self, touch) {
+            (_, None) => false,
+            (Adjacency::Any, _) => true,
+            (a, Some(b)) => *a == b,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum Count {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl Count {
+    pub fn matches(&self, n: usize) -> bool {
+        match self {
+            Count::Exactly(c) => n == *c,
+            Count::AtLeast(c) => n >= *c,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum Aggregation {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Aggregation {
+    pub fn apply(&self, values: impl Iterator<Item = u32>) -> u64 {
+        let values = values.map(u64::from);
+        match self {
+            Aggregation::Product => values.product(),
+            Aggregation::Sum => values.sum(),
+            Aggregation::Max => values.max().unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Query<P> {
+    symbols: P,
+    count: Count,
+    aggregation: Aggregation,
+    adjacency: Adjacency,
+}
+
+impl<P> Query<P>
+where
+    P: Fn(char) -> bool,
+{
+    /// Matches symbols accepted by `symbols` touching at least one number, summing them.
+    pub fn new(symbols: P) -> Self {
+        Self {
+            symbols,
+            count: Count::AtLeast(1),
+            aggregation: Aggregation::Sum,
+            adjacency: Adjacency::Any,
+        }
+    }
+
+    pub fn count(mut self, count: Count) -> Self {
+        self.count = count;
+        self
+    }
+
+    pub fn aggregation(mut self, aggregation: Aggregation) -> Self {
+        self.aggregation = aggregation;
+        self
+    }
+
+    pub fn adjacency(mut self, adjacency: Adjacency) -> Self {
+        self.adjacency = adjacency;
+        self
+    }
+}
+
+/// Cells surrounding the span `start..end` in `row`.
+fn neighbours(row: usize, start: usize, end: usize) -> impl Iterator<Item = (usize, usize)> {
+    let cols = start.saturating_sub(1)..=end;
+    (row.saturating_sub(1)..=row + 1)
+        .flat_map(move |r| cols.clone().map(move |c| (r, c)))
+        .filter(move |(r, c)| *r != row || *c < start || *c >= end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjacency() {
+        let schematic = Schematic::parse("12*12\n.....\n..7..");
+        let gear = schematic.symbol_at(0, 2).unwrap();
+
+        let values = schematic
+            .numbers_adjacent_to(gear)
+            .into_iter()
+            .map(|n| n.value)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![12, 12]);
+        assert_eq!(schematic.gear_ratios(), vec![144]);
+
+        let seven = schematic.number_at(2, 2).unwrap();
+        assert!(schematic.symbols_adjacent_to(seven).is_empty());
+        assert_eq!(schematic.part_numbers().map(|n| n.value).sum::<u32>(), 24);
+    }
+
+    #[test]
+    fn test_query() {
+        let schematic = Schematic::parse("1.2.3\n.#.#.\n4...5\n.....\n6*..7");
+
+        let triples = Query::new(|c| c == '#').count(Count::Exactly(3));
+        let sums = schematic
+            .query(&triples)
+            .map(|(_, v)| v)
+            .collect::<Vec<_>>();
+        assert_eq!(sums, vec![7, 10]);
+
+        let max = Query::new(|c| "#*".contains(c))
+            .count(Count::AtLeast(1))
+            .aggregation(Aggregation::Max)
+            .adjacency(Adjacency::Orthogonal);
+        let maxes = schematic.query(&max).map(|(_, v)| v).collect::<Vec<_>>();
+        assert_eq!(maxes, vec![6]);
+
+        let diagonal = Query::new(|c| c == '#')
+            .aggregation(Aggregation::Product)
+            .adjacency(Adjacency::Diagonal);
+        let products = schematic
+            .query(&diagonal)
+            .map(|(_, v)| v)
+            .collect::<Vec<_>>();
+        assert_eq!(products, vec![8, 30]);
+    }
+}
@@ -0,0 +1,287 @@
+use itertools::Itertools;
+use std::{
+    collections::{HashMap, VecDeque},
+    ops::Range,
+    str::Lines,
+};
+
+mod piecewise;
+pub use piecewise::{intersect, normalize, PiecewiseMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapEntry {
+    pub destination: u64,
+    pub source: u64,
+    pub range_length: u64,
+}
+
+impl MapEntry {
+    pub fn new(destination: u64, source: u64, range_length: u64) -> Self {
+        Self {
+            destination,
+            source,
+            range_length,
+        }
+    }
+
+    pub fn get(&self, value: u64) -> Option<u64> {
+        let index = value.checked_sub(self.source)?;
+        (index < self.range_length).then(|| self.destination + index)
+    }
+
+    /// Source values of the entry, clamped to end at `u64::MAX`.
+    pub fn source_range(&self) -> Range<u64> {
+        self.source..self.source.saturating_add(self.range_length)
+    }
+
+    /// Exclusive end of the source values, which may be 2^64.
+    pub(crate) fn source_end(&self) -> i128 {
+        self.source as i128 + self.range_length as i128
+    }
+
+    pub fn get_offset(&self) -> i64 {
+        self.destination as i64 - self.source as i64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryMap<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub entries: Vec<MapEntry>,
+}
+
+impl CategoryMap<'_> {
+    pub fn get(&self, value: u64) -> u64 {
+        self.entries
+            .iter()
+            .find_map(|entry| entry.get(value))
+            .unwrap_or(value)
+    }
+
+    pub fn piecewise(&self) -> PiecewiseMap {
+        PiecewiseMap::from_entries(&self.entries)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Almanac<'a> {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<CategoryMap<'a>>,
+}
+
+impl<'a> Almanac<'a> {
+    pub fn parse(input: &'a str) -> Self {
+        let mut almanac = Self::default();
+
+        input.split("\n\n").for_each(|part| {
+            let mut lines = part.lines();
+            let (key, rest) = lines
+                .next()
+                .unwrap()
+                .split_once(':')
+                .expect("missing section header");
+            if key == "seeds" {
+                almanac.seeds = rest
+                    .split_ascii_whitespace()
+                    .map(|s| s.parse::<u64>().unwrap())
+                    .collect();
+            } else {
+                let (from, to) = key
+                    .strip_suffix(" map")
+                    .and_then(|k| k.split_once("-to-"))
+                    .unwrap_or_else(|| panic!("Invalid key: {}", key));
+                almanac.maps.push(CategoryMap {
+                    from,
+                    to,
+                    entries: process_map(lines),
+                });
+            }
+        });
+
+        almanac
+    }
+
+    /// Seeds read as pairs of range start and length, clamped to end at `u64::MAX`.
+    pub fn seed_ranges(&self) -> Vec<Range<u64>> {
+        self.seeds
+            .iter()
+            .tuples()
+            .map(|(start, length)| *start..start.saturating_add(*length))
+            .collect()
+    }
+
+    pub fn categories(&self) -> Vec<&'a str> {
+        self.maps
+            .iter()
+            .flat_map(|m| [m.from, m.to])
+            .unique()
+            .collect()
+    }
+
+    /// Shortest chain of maps converting `from` into `to`.
+    pub fn path(&self, from: &str, to: &str) -> Option<Vec<&CategoryMap<'a>>> {
+        let mut previous: HashMap<&str, Option<usize>> = HashMap::from([(from, None)]);
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                let mut path = Vec::new();
+                let mut current = previous[category];
+                while let Some(i) = current {
+                    path.push(&self.maps[i]);
+                    current = previous[self.maps[i].from];
+                }
+                path.reverse();
+                return Some(path);
+            }
+            self.maps
+                .iter()
+                .enumerate()
+                .filter(|(_, m)| m.from == category)
+                .for_each(|(i, m)| {
+                    if !previous.contains_key(m.to) {
+                        previous.insert(m.to, Some(i));
+                        queue.push_back(m.to);
+                    }
+                });
+        }
+
+        None
+    }
+
+    /// All maps between `from` and `to` composed into one.
+    pub fn piecewise(&self, from: &str, to: &str) -> Option<PiecewiseMap> {
+        self.path(from, to).map(|path| {
+            path.into_iter().fold(PiecewiseMap::identity(), |acc, map| {
+                acc.compose(&map.piecewise())
+            })
+        })
+    }
+
+    pub fn convert(&self, value: u64, from: &str, to: &str) -> Option<u64> {
+        self.path(from, to).map(|path| {
+            path.into_iter()
+                .fold(value, |current, map| map.get(current))
+        })
+    }
+
+    /// Every category passed on the way from `from` to `to` with the value in it,
+    /// starting with `value` itself.
+    pub fn trace(&self, value: u64, from: &'a str, to: &str) -> Option<Vec<(&'a str, u64)>> {
+        self.path(from, to).map(|path| {
+            path.into_iter()
+                .fold(vec![(from, value)], |mut trace, map| {
+                    let current = trace.last().unwrap().1;
+                    trace.push((map.to, map.get(current)));
+                    trace
+                })
+        })
+    }
+
+    /// Values of `from` that convert into any of the `to` ranges.
+    pub fn preimage(&self, from: &str, to: &str, ranges: &[Range<u64>]) -> Option<Vec<Range<u64>>> {
+        self.piecewise(from, to).map(|map| map.preimage(ranges))
+    }
+
+    /// Trace of the smallest input among `ranges` reaching the lowest possible `to` value.
+    pub fn lowest(
+        &self,
+        from: &'a str,
+        to: &str,
+        ranges: &[Range<u64>],
+    ) -> Option<Vec<(&'a str, u64)>> {
+        let map = self.piecewise(from, to)?;
+        let min = map.min_over(ranges)?;
+        let candidates = intersect(
+            &map.preimage(&[Range {
+                start: min,
+                end: min + 1,
+            }]),
+            ranges,
+        );
+        self.trace(candidates.first()?.start, from, to)
+    }
+}
+
+impl<'a> From<&'a str> for Almanac<'a> {
+    fn from(s: &'a str) -> Self {
+        Self::parse(s)
+    }
+}
+
+fn process_map(lines: Lines) -> Vec<MapEntry> {
+    lines
+        .map(|l| {
+            let parts = l.split_ascii_whitespace().collect::<Vec<_>>();
+            MapEntry::new(
+                parts[0].parse::<u64>().unwrap(),
+                parts[1].parse::<u64>().unwrap(),
+                parts[2].parse::<u64>().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15\n\nfertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4\n\nwater-to-light map:\n88 18 7\n18 25 70\n\nlight-to-temperature map:\n45 77 23\n81 45 19\n68 64 13\n\ntemperature-to-humidity map:\n0 69 1\n1 0 69\n\nhumidity-to-location map:\n60 56 37\n56 93 4";
+
+    #[test]
+    fn test_convert() {
+        let almanac = Almanac::parse(INPUT);
+        assert_eq!(almanac.categories().len(), 8);
+        assert_eq!(almanac.convert(79, "seed", "water"), Some(81));
+        assert_eq!(almanac.convert(74, "light", "location"), Some(82));
+        assert_eq!(almanac.convert(14, "seed", "seed"), Some(14));
+        assert_eq!(almanac.convert(14, "location", "seed"), None);
+
+        let top = Almanac::parse("seeds: 0 1\n\nseed-to-location map:\n0 1 18446744073709551615");
+        assert_eq!(
+            top.convert(u64::MAX, "seed", "location"),
+            Some(u64::MAX - 1)
+        );
+        assert_eq!(top.convert(0, "seed", "location"), Some(0));
+
+        let short = Almanac::parse("seeds: 1 2\n\nseed-to-location map:\n10 0 5");
+        assert_eq!(short.convert(2, "seed", "location"), Some(12));
+        assert_eq!(short.seed_ranges(), vec![1..3]);
+
+        let huge = Almanac::parse("seeds: 5 18446744073709551615");
+        assert_eq!(huge.seed_ranges(), vec![5..u64::MAX]);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let almanac = Almanac::parse(INPUT);
+        assert_eq!(
+            almanac.trace(79, "seed", "location"),
+            Some(vec![
+                ("seed", 79),
+                ("soil", 81),
+                ("fertilizer", 81),
+                ("water", 81),
+                ("light", 74),
+                ("temperature", 78),
+                ("humidity", 78),
+                ("location", 82)
+            ])
+        );
+
+        let seeds = almanac
+            .preimage("seed", "location", &[46..47, 1000..1001])
+            .unwrap();
+        assert!(seeds.iter().any(|r| r.contains(&82)));
+        assert!(seeds
+            .iter()
+            .flat_map(|r| r.clone())
+            .all(|s| [Some(46), Some(1000)].contains(&almanac.convert(s, "seed", "location"))));
+
+        let lowest = almanac
+            .lowest("seed", "location", &almanac.seed_ranges())
+            .unwrap();
+        assert_eq!(lowest.first(), Some(&("seed", 82)));
+        assert_eq!(lowest.last(), Some(&("location", 46)));
+    }
+}
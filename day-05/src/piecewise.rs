@@ -0,0 +1,256 @@
+use crate::MapEntry;
+use std::{cmp::Reverse, collections::BinaryHeap, ops::Range};
+
+/// Exclusive upper bound of the `u64` domain.
+const END: i128 = 1 << 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Segment {
+    start: u64,
+    offset: i128,
+}
+
+/// A total map on `u64` made of consecutive segments, each shifting its
+/// values by a constant offset. Values outside every entry map to themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    segments: Vec<Segment>,
+}
+
+impl PiecewiseMap {
+    pub fn identity() -> Self {
+        Self {
+            segments: vec![Segment {
+                start: 0,
+                offset: 0,
+            }],
+        }
+    }
+
+    /// Builds the map from entries, with the first matching entry taking precedence.
+    ///
+    /// Sweeps the breakpoints in order while a heap holds the entries started so far,
+    /// the earliest one in input order on top.
+    pub fn from_entries(entries: &[MapEntry]) -> Self {
+        let mut breakpoints = entries
+            .iter()
+            .flat_map(|e| [e.source as i128, e.source_end()])
+            .chain([0])
+            .filter(|b| *b < END)
+            .collect::<Vec<_>>();
+        breakpoints.sort_unstable();
+        breakpoints.dedup();
+
+        let mut by_source = entries.iter().enumerate().collect::<Vec<_>>();
+        by_source.sort_unstable_by_key(|(_, e)| e.source);
+        let mut pending = by_source.into_iter().peekable();
+        let mut active = BinaryHeap::new();
+
+        let segments = breakpoints
+            .into_iter()
+            .map(|start| {
+                while let Some((i, e)) = pending.next_if(|(_, e)| e.source as i128 <= start) {
+                    let offset = e.destination as i128 - e.source as i128;
+                    active.push(Reverse((i, e.source_end(), offset)));
+                }
+                while active
+                    .peek()
+                    .is_some_and(|Reverse((_, end, _))| *end <= start)
+                {
+                    active.pop();
+                }
+                let offset = active.peek().map_or(0, |Reverse((_, _, offset))| *offset);
+                Segment {
+                    start: start as u64,
+                    offset,
+                }
+            })
+            .collect();
+
+        Self::merged(segments)
+    }
+
+    fn merged(segments: Vec<Segment>) -> Self {
+        let mut merged: Vec<Segment> = Vec::with_capacity(segments.len());
+        for segment in segments {
+            match merged.last() {
+                Some(last) if last.offset == segment.offset => {}
+                _ => merged.push(segment),
+            }
+        }
+        Self { segments: merged }
+    }
+
+    fn index_of(&self, value: u64) -> usize {
+        self.segments.partition_point(|s| s.start <= value) - 1
+    }
+
+    fn end_of(&self, index: usize) -> i128 {
+        self.segments
+            .get(index + 1)
+            .map(|s| s.start as i128)
+            .unwrap_or(END)
+    }
+
+    pub fn get(&self, value: u64) -> u64 {
+        (value as i128 + self.segments[self.index_of(value)].offset) as u64
+    }
+
+    /// The map applying `self` first and `next` afterwards.
+    pub fn compose(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let mut segments = Vec::new();
+        for (i, segment) in self.segments.iter().enumerate() {
+            let mut image = segment.start as i128 + segment.offset;
+            let image_end = self.end_of(i) + segment.offset;
+            while image < image_end {
+                let j = next.index_of(image as u64);
+                segments.push(Segment {
+                    start: (image - segment.offset) as u64,
+                    offset: segment.offset + next.segments[j].offset,
+                });
+                image = image_end.min(next.end_of(j));
+            }
+        }
+        Self::merged(segments)
+    }
+
+    /// Image of a set of ranges as sorted, disjoint ranges, clamped to end at `u64::MAX`.
+    pub fn apply(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut mapped = Vec::new();
+        for range in normalize(ranges.to_vec()) {
+            let mut i = self.index_of(range.start);
+            let mut start = range.start as i128;
+            while start < range.end as i128 {
+                let end = self.end_of(i).min(range.end as i128);
+                let offset = self.segments[i].offset;
+                mapped.push(clamp(start + offset)..clamp(end + offset));
+                start = end;
+                i += 1;
+            }
+        }
+        normalize(mapped)
+    }
+
+    /// All values mapping into the given ranges, as sorted, disjoint ranges,
+    /// clamped to end at `u64::MAX`.
+    pub fn preimage(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let targets = normalize(ranges.to_vec());
+        let mut preimage = Vec::new();
+        for (i, segment) in self.segments.iter().enumerate() {
+            let image_start = segment.start as i128 + segment.offset;
+            let image_end = self.end_of(i) + segment.offset;
+            let first = targets.partition_point(|t| (t.end as i128) <= image_start);
+            targets[first..]
+                .iter()
+                .take_while(|t| (t.start as i128) < image_end)
+                .for_each(|t| {
+                    let start = image_start.max(t.start as i128) - segment.offset;
+                    let end = image_end.min(t.end as i128) - segment.offset;
+                    preimage.push(clamp(start)..clamp(end));
+                });
+        }
+        normalize(preimage)
+    }
+
+    /// Smallest value any of the ranges maps to.
+    pub fn min_over(&self, ranges: &[Range<u64>]) -> Option<u64> {
+        self.apply(ranges).first().map(|r| r.start)
+    }
+}
+
+impl From<&[MapEntry]> for PiecewiseMap {
+    fn from(entries: &[MapEntry]) -> Self {
+        Self::from_entries(entries)
+    }
+}
+
+/// Range bound in the `u64` domain, with 2^64 and beyond lowered to `u64::MAX`.
+fn clamp(bound: i128) -> u64 {
+    bound.clamp(0, u64::MAX as i128) as u64
+}
+
+/// Values contained in both sets of ranges.
+pub fn intersect(a: &[Range<u64>], b: &[Range<u64>]) -> Vec<Range<u64>> {
+    let b = normalize(b.to_vec());
+    let mut intersection = normalize(a.to_vec())
+        .into_iter()
+        .flat_map(|r| {
+            b.iter()
+                .map(move |other| r.start.max(other.start)..r.end.min(other.end))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    intersection.retain(|r| !r.is_empty());
+    normalize(intersection)
+}
+
+/// Sorts the ranges and merges overlapping or touching ones.
+pub fn normalize(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    ranges.retain(|r| !r.is_empty());
+    ranges.sort_unstable_by_key(|r| r.start);
+    let mut merged: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose_and_apply() {
+        let first =
+            PiecewiseMap::from_entries(&[MapEntry::new(50, 98, 2), MapEntry::new(52, 50, 48)]);
+        let second = PiecewiseMap::from_entries(&[
+            MapEntry::new(0, 15, 37),
+            MapEntry::new(37, 52, 2),
+            MapEntry::new(39, 0, 15),
+        ]);
+        let composed = first.compose(&second);
+
+        (0..200).for_each(|v| assert_eq!(composed.get(v), second.get(first.get(v))));
+        assert_eq!(composed.get(u64::MAX), u64::MAX);
+
+        assert_eq!(first.apply(&[96..101, 45..50]), vec![45..52, 98..101]);
+        assert_eq!(composed.min_over(&[79..93, 55..68]), Some(57));
+        assert_eq!(composed.min_over(&[]), None);
+
+        assert_eq!(first.preimage(&[50..52, 200..201]), vec![98..100, 200..201]);
+        assert_eq!(first.preimage(&[45..48, 47..53]), vec![45..51, 98..100]);
+        assert_eq!(
+            intersect(&[0..10, 20..30], &[5..25, 40..50]),
+            vec![5..10, 20..25]
+        );
+    }
+
+    #[test]
+    fn test_top_of_domain() {
+        let up = PiecewiseMap::from_entries(&[MapEntry::new(1, 0, u64::MAX)]);
+        assert_eq!(up.get(u64::MAX - 1), u64::MAX);
+        let below_top = 0..u64::MAX;
+        assert_eq!(
+            up.apply(std::slice::from_ref(&below_top)),
+            vec![1..u64::MAX]
+        );
+        assert_eq!(up.min_over(&[below_top]), Some(1));
+
+        let down = PiecewiseMap::from_entries(&[MapEntry::new(0, 1, u64::MAX)]);
+        assert_eq!(down.get(u64::MAX), u64::MAX - 1);
+        assert_eq!(down.get(0), 0);
+        let from_five = 5..u64::MAX;
+        assert_eq!(down.preimage(&[from_five]), vec![6..u64::MAX]);
+
+        let overlapping = PiecewiseMap::from_entries(&[
+            MapEntry::new(100, 10, 5),
+            MapEntry::new(200, 0, u64::MAX),
+        ]);
+        assert_eq!(overlapping.get(12), 102);
+        assert_eq!(overlapping.get(15), 215);
+        assert_eq!(overlapping.get(u64::MAX), u64::MAX);
+    }
+}
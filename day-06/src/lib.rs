@@ -0,0 +1,215 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, newline, space1},
+    multi::separated_list1,
+    sequence::{pair, preceded, separated_pair},
+    IResult,
+};
+use num::{integer::Roots, Integer};
+use std::{fmt::Debug, ops::RangeInclusive, str::FromStr};
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum Mode {
+    /// Every column is a separate race
+    Spaced,
+    /// All columns form the digits of one race
+    Kerned,
+}
+
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct Race<T> {
+    pub time: T,
+    pub record: T,
+}
+
+impl<T: Integer + Roots + Clone> Race<T> {
+    pub fn winning_range(&self) -> Option<RangeInclusive<T>> {
+        winning_range(self.time.clone(), self.record.clone())
+    }
+
+    pub fn count_wins(&self) -> T {
+        count_wins(self.time.clone(), self.record.clone())
+    }
+
+    pub fn distance(&self, hold_time: T) -> T {
+        calc_distance(hold_time, self.time.clone())
+    }
+
+    /// How far holding for `hold_time` beats the record, if it does.
+    pub fn margin(&self, hold_time: T) -> Option<T> {
+        let distance = self.distance(hold_time);
+        (distance > self.record).then(|| distance - self.record.clone())
+    }
+
+    /// Hold time reaching the furthest distance, together with that distance.
+    pub fn optimal(&self) -> (T, T) {
+        let hold_time = self.time.clone() / (T::one() + T::one());
+        (hold_time.clone(), self.distance(hold_time))
+    }
+
+    pub fn report(&self) -> RaceReport<T> {
+        let (optimal_hold, optimal_distance) = self.optimal();
+        RaceReport {
+            race: self.clone(),
+            winning: self.winning_range(),
+            wins: self.count_wins(),
+            best_margin: self.margin(optimal_hold.clone()),
+            optimal_hold,
+            optimal_distance,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct RaceReport<T> {
+    pub race: Race<T>,
+    pub winning: Option<RangeInclusive<T>>,
+    pub wins: T,
+    pub optimal_hold: T,
+    pub optimal_distance: T,
+    pub best_margin: Option<T>,
+}
+
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct Report<T> {
+    pub races: Vec<RaceReport<T>>,
+    /// Product of the winning strategy counts of all races
+    pub product: T,
+    pub total_wins: T,
+}
+
+pub fn report<T: Integer + Roots + Clone>(races: &[Race<T>]) -> Report<T> {
+    let races = races.iter().map(Race::report).collect::<Vec<_>>();
+    Report {
+        product: races.iter().fold(T::one(), |acc, r| acc * r.wins.clone()),
+        total_wins: races.iter().fold(T::zero(), |acc, r| acc + r.wins.clone()),
+        races,
+    }
+}
+
+pub fn parse<T>(input: &str, mode: Mode) -> Vec<Race<T>>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    let (times, records) = separated_pair(time_parser, newline, distance_parser)(input)
+        .expect("invalid input")
+        .1;
+
+    match mode {
+        Mode::Spaced => times
+            .into_iter()
+            .zip(records)
+            .map(|(time, record)| Race {
+                time: time.parse().expect("Invalid digit"),
+                record: record.parse().expect("Invalid digit"),
+            })
+            .collect(),
+        Mode::Kerned => vec![Race {
+            time: times.join("").parse().expect("Invalid digit"),
+            record: records.join("").parse().expect("Invalid digit"),
+        }],
+    }
+}
+
+fn time_parser(i: &str) -> IResult<&str, Vec<&str>> {
+    preceded(pair(tag("Time:"), space1), separated_list1(space1, digit1))(i)
+}
+
+fn distance_parser(i: &str) -> IResult<&str, Vec<&str>> {
+    preceded(
+        pair(tag("Distance:"), space1),
+        separated_list1(space1, digit1),
+    )(i)
+}
+
+pub fn calc_distance<T: Integer + Clone>(hold_time: T, total_time: T) -> T {
+    if hold_time > total_time {
+        return T::zero();
+    }
+    hold_time.clone() * (total_time - hold_time)
+}
+
+/// Hold times travelling strictly further than `record`.
+///
+/// The winning hold times lie between the roots of `h * (time - h) = record`,
+/// so they are found with an integer square root and a small correction.
+pub fn winning_range<T: Integer + Roots + Clone>(time: T, record: T) -> Option<RangeInclusive<T>> {
+    let two = T::one() + T::one();
+    let four = two.clone() + two.clone();
+    let squared = time.clone() * time.clone();
+    let quadrupled = four * record.clone();
+    if quadrupled >= squared {
+        return None;
+    }
+
+    let root = (squared - quadrupled).sqrt();
+    let peak = time.clone() / two.clone();
+    let mut lo = (time.clone() - root) / two;
+    while lo <= peak && calc_distance(lo.clone(), time.clone()) <= record {
+        lo = lo + T::one();
+    }
+    if lo > peak {
+        return None;
+    }
+
+    let hi = time - lo.clone();
+    Some(lo..=hi)
+}
+
+pub fn count_wins<T: Integer + Roots + Clone>(time: T, record: T) -> T {
+    winning_range(time, record)
+        .map(|r| {
+            let (lo, hi) = r.into_inner();
+            hi - lo + T::one()
+        })
+        .unwrap_or_else(T::zero)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigUint;
+
+    fn brute_force(time: u64, record: u64) -> u64 {
+        (1..time)
+            .filter(|t| calc_distance(*t, time) > record)
+            .count() as u64
+    }
+
+    #[test]
+    fn test_count_wins() {
+        (0..60).for_each(|time| {
+            (0..=time * time / 4 + 1)
+                .for_each(|record| assert_eq!(count_wins(time, record), brute_force(time, record)))
+        });
+
+        assert_eq!(count_wins(30, 200), 9);
+        assert_eq!(winning_range(30, 200), Some(11..=19));
+        assert_eq!(winning_range(10, 25), None);
+        assert_eq!(count_wins(71530u128, 940200), 71503);
+
+        let time = 10u128.pow(19) + 7;
+        let record = 10u128.pow(37) * 2 + 12345;
+        let big = count_wins(BigUint::from(time), BigUint::from(record));
+        assert_eq!(big, BigUint::from(count_wins(time, record)));
+    }
+
+    #[test]
+    fn test_report() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+
+        let report = report(&parse::<u64>(input, Mode::Spaced));
+        assert_eq!(report.product, 288);
+        assert_eq!(report.total_wins, 4 + 8 + 9);
+        assert_eq!(report.races[0].winning, Some(2..=5));
+        assert_eq!(report.races[0].optimal_hold, 3);
+        assert_eq!(report.races[0].optimal_distance, 12);
+        assert_eq!(report.races[0].best_margin, Some(3));
+        assert_eq!(report.races[2].race.margin(10), None);
+        assert_eq!(report.races[2].race.margin(11), Some(9));
+
+        let kerned = parse::<BigUint>(input, Mode::Kerned);
+        assert_eq!(kerned[0].count_wins(), BigUint::from(71503u32));
+    }
+}
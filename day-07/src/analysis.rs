@@ -0,0 +1,200 @@
+use crate::{Rank, Ruleset, TieBreak};
+use std::{cmp::Ordering, collections::BTreeMap, fmt};
+
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct Distribution {
+    /// Number of dealt hands per rank, cards in order
+    pub counts: BTreeMap<Rank, u128>,
+    pub total: u128,
+}
+
+impl Distribution {
+    pub fn probability(&self, rank: Rank) -> f64 {
+        self.counts.get(&rank).copied().unwrap_or(0) as f64 / self.total as f64
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.counts.iter().try_for_each(|(rank, count)| {
+            writeln!(
+                f,
+                "{:<12} {:>12} {:>9.5}%",
+                format!("{:?}", rank),
+                count,
+                self.probability(*rank) * 100.0
+            )
+        })
+    }
+}
+
+/// Exact number of hands of every rank among all ordered deals of the ruleset.
+///
+/// Instead of enumerating hands, every split of the hand into wildcards and
+/// group sizes of the remaining cards is counted once.
+pub fn rank_distribution(ruleset: &Ruleset) -> Distribution {
+    let n = ruleset.hand_size;
+    let wild = ruleset.wildcards.len() as u128;
+    let natural = ruleset.order.len() - ruleset.wildcards.len();
+    let max_jokers = if wild == 0 { 0 } else { n };
+
+    let mut counts = BTreeMap::new();
+    for jokers in 0..=max_jokers {
+        for groups in partitions(n - jokers, n - jokers, natural) {
+            let ways = multinomial(n, jokers, &groups)
+                * wild.pow(jokers as u32)
+                * label_assignments(natural, &groups);
+
+            let mut with_jokers = groups.clone();
+            match with_jokers.first_mut() {
+                Some(largest) => *largest += jokers,
+                None => with_jokers.push(jokers),
+            }
+            *counts.entry(Rank::from_groups(&with_jokers)).or_insert(0) += ways;
+        }
+    }
+
+    Distribution {
+        total: counts.values().sum(),
+        counts,
+    }
+}
+
+/// Expected total winnings when every bid gets an independent, uniformly random hand.
+pub fn expected_winnings(ruleset: &Ruleset, bids: &[u32]) -> f64 {
+    let equal = tie_probability(ruleset);
+    let weaker = (1.0 - equal) / 2.0;
+
+    bids.iter()
+        .enumerate()
+        .map(|(i, bid)| {
+            let position = 1.0
+                + bids
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, other)| {
+                        weaker
+                            + match bid.cmp(other) {
+                                Ordering::Greater => equal,
+                                Ordering::Equal => equal / 2.0,
+                                Ordering::Less => 0.0,
+                            }
+                    })
+                    .sum::<f64>();
+            *bid as f64 * position
+        })
+        .sum()
+}
+
+/// Probability of two random hands comparing equal before their bids are looked at.
+fn tie_probability(ruleset: &Ruleset) -> f64 {
+    let n = ruleset.hand_size;
+    let labels = ruleset.order.len();
+    let total = (labels as f64).powi(n as i32);
+
+    match ruleset.tie_break {
+        TieBreak::FirstCard => 1.0 / total,
+        TieBreak::HighestCard => partitions(n, n, labels)
+            .into_iter()
+            .map(|groups| {
+                let hands = multinomial(n, 0, &groups) as f64 / total;
+                label_assignments(labels, &groups) as f64 * hands * hands
+            })
+            .sum(),
+        TieBreak::None => {
+            let distribution = rank_distribution(ruleset);
+            distribution
+                .counts
+                .keys()
+                .map(|r| distribution.probability(*r).powi(2))
+                .sum()
+        }
+    }
+}
+
+/// Partitions of `n` into at most `parts` parts no larger than `max`, largest first.
+fn partitions(n: usize, max: usize, parts: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+    if parts == 0 {
+        return Vec::new();
+    }
+    (1..=max.min(n))
+        .rev()
+        .flat_map(|first| {
+            partitions(n - first, first, parts - 1)
+                .into_iter()
+                .map(move |mut rest| {
+                    rest.insert(0, first);
+                    rest
+                })
+        })
+        .collect()
+}
+
+fn binomial(n: usize, k: usize) -> u128 {
+    (0..k).fold(1, |acc, i| acc * (n - i) as u128 / (i + 1) as u128)
+}
+
+/// Ways to place `jokers` and the groups on `n` ordered positions.
+fn multinomial(n: usize, jokers: usize, groups: &[usize]) -> u128 {
+    let mut remaining = n;
+    std::iter::once(&jokers)
+        .chain(groups)
+        .map(|g| {
+            let ways = binomial(remaining, *g);
+            remaining -= g;
+            ways
+        })
+        .product()
+}
+
+/// Ways to give every group a distinct label, groups of equal size being interchangeable.
+fn label_assignments(labels: usize, groups: &[usize]) -> u128 {
+    let falling = (0..groups.len()).fold(1, |acc, i| acc * (labels - i) as u128);
+    let mut same_size = BTreeMap::new();
+    groups
+        .iter()
+        .for_each(|g| *same_size.entry(*g).or_insert(0u128) += 1);
+    same_size
+        .values()
+        .fold(falling, |acc, m| acc / (1..=*m).product::<u128>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn test_rank_distribution() {
+        let standard = rank_distribution(&Ruleset::camel_cards());
+        assert_eq!(standard.total, 13u128.pow(5));
+        assert_eq!(standard.counts[&Rank::FiveOfAKind], 13);
+        assert_eq!(standard.counts[&Rank::FourOfAKind], 780);
+        assert_eq!(standard.counts[&Rank::FullHouse], 1560);
+
+        for mut ruleset in [Ruleset::camel_cards(), Ruleset::jokers()] {
+            ruleset.hand_size = 4;
+            let mut brute_force = BTreeMap::new();
+            (0..4)
+                .map(|_| ruleset.order.iter())
+                .multi_cartesian_product()
+                .for_each(|cards| {
+                    let cards = cards.into_iter().collect::<String>();
+                    *brute_force.entry(ruleset.evaluate(&cards)).or_insert(0) += 1;
+                });
+            assert_eq!(rank_distribution(&ruleset).counts, brute_force);
+        }
+    }
+
+    #[test]
+    fn test_expected_winnings() {
+        let ruleset = Ruleset::jokers();
+        assert_eq!(expected_winnings(&ruleset, &[5]), 5.0);
+        assert!((expected_winnings(&ruleset, &[1, 1]) - 3.0).abs() < 1e-9);
+        assert!(expected_winnings(&ruleset, &[1, 2]) > 4.5);
+    }
+}
@@ -0,0 +1,434 @@
+use itertools::Itertools;
+use std::{cmp::Ordering, fmt};
+
+mod analysis;
+pub use analysis::{expected_winnings, rank_distribution, Distribution};
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq, PartialOrd, Ord, Hash)]
+pub enum Rank {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl Rank {
+    /// Rank of a hand given its group sizes in descending order.
+    /// Larger groups than five still count as five of a kind.
+    pub fn from_groups(groups: &[usize]) -> Self {
+        let mut groups = groups.iter().copied();
+        match (groups.next().unwrap_or(0), groups.next().unwrap_or(0)) {
+            (a, _) if a >= 5 => Rank::FiveOfAKind,
+            (4, _) => Rank::FourOfAKind,
+            (3, b) if b >= 2 => Rank::FullHouse,
+            (3, _) => Rank::ThreeOfAKind,
+            (2, 2) => Rank::TwoPair,
+            (2, _) => Rank::OnePair,
+            _ => Rank::HighCard,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum TieBreak {
+    /// Compare card strengths in the order the cards were dealt
+    FirstCard,
+    /// Compare card strengths from strongest to weakest card
+    HighestCard,
+    /// Hands of equal rank are only ordered by bid, the lower bid placing weaker
+    None,
+}
+
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct Ruleset {
+    /// Card labels from weakest to strongest
+    pub order: Vec<char>,
+    /// Cards joining whichever group makes the hand strongest
+    pub wildcards: Vec<char>,
+    pub hand_size: usize,
+    pub tie_break: TieBreak,
+}
+
+impl Ruleset {
+    pub fn new(order: &str, wildcards: &str, hand_size: usize, tie_break: TieBreak) -> Self {
+        Self {
+            order: order.chars().collect(),
+            wildcards: wildcards.chars().collect(),
+            hand_size,
+            tie_break,
+        }
+    }
+
+    /// Rules of part 1
+    pub fn camel_cards() -> Self {
+        Self::new("23456789TJQKA", "", 5, TieBreak::FirstCard)
+    }
+
+    /// Rules of part 2, with `J` as the weakest card and a wildcard
+    pub fn jokers() -> Self {
+        Self::new("J23456789TQKA", "J", 5, TieBreak::FirstCard)
+    }
+
+    pub fn strength(&self, card: char) -> usize {
+        self.order
+            .iter()
+            .position(|c| *c == card)
+            .unwrap_or_else(|| panic!("invalid card: {}", card))
+    }
+
+    pub fn is_wildcard(&self, card: char) -> bool {
+        self.wildcards.contains(&card)
+    }
+
+    /// Non-wild cards grouped by label, largest and then strongest group first.
+    pub fn groups(&self, cards: &str) -> Vec<(char, usize)> {
+        let mut groups = cards
+            .chars()
+            .filter(|c| !self.is_wildcard(*c))
+            .counts()
+            .into_iter()
+            .collect::<Vec<_>>();
+        groups.sort_by(|(c1, g1), (c2, g2)| match g2.cmp(g1) {
+            Ordering::Equal => self.strength(*c2).cmp(&self.strength(*c1)),
+            x => x,
+        });
+        groups
+    }
+
+    pub fn evaluate(&self, cards: &str) -> Rank {
+        let jokers = cards.chars().filter(|c| self.is_wildcard(*c)).count();
+        let mut groups = self
+            .groups(cards)
+            .into_iter()
+            .map(|(_, g)| g)
+            .collect::<Vec<_>>();
+        match groups.first_mut() {
+            Some(largest) => *largest += jokers,
+            None => groups.push(jokers),
+        }
+        Rank::from_groups(&groups)
+    }
+
+    fn tie_break_key(&self, cards: &str) -> Vec<usize> {
+        let mut key = cards.chars().map(|c| self.strength(c)).collect::<Vec<_>>();
+        match self.tie_break {
+            TieBreak::FirstCard => {}
+            TieBreak::HighestCard => key.sort_unstable_by(|a, b| b.cmp(a)),
+            TieBreak::None => key.clear(),
+        }
+        key
+    }
+
+    pub fn hand(&self, cards: &str, bid: u32) -> Hand {
+        if cards.chars().count() != self.hand_size {
+            panic!("invalid hand: {}", cards);
+        }
+        Hand {
+            rank: self.evaluate(cards),
+            key: self.tie_break_key(cards),
+            bid,
+            cards: cards.to_string(),
+        }
+    }
+
+    pub fn process_hand(&self, input: &str) -> Hand {
+        let (cards, bid) = input.split_once(' ').expect("invalid input");
+        self.hand(cards, bid.parse().expect("invalid bid"))
+    }
+
+    /// The hand with every wildcard replaced by the card it stands in for.
+    pub fn substitute(&self, cards: &str) -> String {
+        let replacement = self.groups(cards).first().map(|(c, _)| *c).or_else(|| {
+            self.order
+                .iter()
+                .rev()
+                .find(|c| !self.is_wildcard(**c))
+                .copied()
+        });
+        cards
+            .chars()
+            .map(|c| match replacement {
+                Some(r) if self.is_wildcard(c) => r,
+                _ => c,
+            })
+            .collect()
+    }
+
+    /// What decides the order between two hands.
+    pub fn decide(&self, ours: &Hand, theirs: &Hand) -> Decider {
+        if ours.rank != theirs.rank {
+            return Decider::Rank {
+                ours: ours.rank,
+                theirs: theirs.rank,
+            };
+        }
+        if let Some((index, (a, b))) = ours
+            .key
+            .iter()
+            .zip(&theirs.key)
+            .enumerate()
+            .find(|(_, (a, b))| a != b)
+        {
+            return Decider::Card {
+                index,
+                ours: self.order[*a],
+                theirs: self.order[*b],
+            };
+        }
+        if ours.bid != theirs.bid {
+            return Decider::Bid {
+                ours: ours.bid,
+                theirs: theirs.bid,
+            };
+        }
+        Decider::Tie
+    }
+
+    /// Every hand of the input from weakest to strongest, with why it placed there.
+    pub fn explain(&self, input: &str) -> Vec<Explanation> {
+        let mut hands = input
+            .lines()
+            .map(|l| self.process_hand(l))
+            .collect::<Vec<_>>();
+        hands.sort();
+
+        hands
+            .iter()
+            .enumerate()
+            .map(|(i, hand)| {
+                let substituted = self.substitute(&hand.cards);
+                Explanation {
+                    position: i + 1,
+                    winnings: (i + 1) as u64 * hand.bid as u64,
+                    groups: self.groups(&substituted),
+                    substituted,
+                    below: i.checked_sub(1).map(|j| self.decide(hand, &hands[j])),
+                    above: hands.get(i + 1).map(|next| self.decide(hand, next)),
+                    hand: hand.clone(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn total_winnings(&self, input: &str) -> u64 {
+        calc_total_winnings(input.lines().map(|l| self.process_hand(l)).collect())
+    }
+}
+
+/// Hands order by rank, then the tie-break key, then bid, so equal hands with a lower
+/// bid place weaker. Hands equal in all three are kept in the order of their labels.
+#[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord)]
+pub struct Hand {
+    pub rank: Rank,
+    /// Card strengths compared after the rank, as given by the tie-break policy
+    pub key: Vec<usize>,
+    pub bid: u32,
+    pub cards: String,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum Decider {
+    Rank {
+        ours: Rank,
+        theirs: Rank,
+    },
+    /// First differing card of the tie-break key
+    Card {
+        index: usize,
+        ours: char,
+        theirs: char,
+    },
+    /// Equal rank and key, the lower bid placing weaker
+    Bid {
+        ours: u32,
+        theirs: u32,
+    },
+    /// Equal rank, key and bid
+    Tie,
+}
+
+impl fmt::Display for Decider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Decider::Rank { ours, theirs } => write!(f, "rank {:?} vs {:?}", ours, theirs),
+            Decider::Card {
+                index,
+                ours,
+                theirs,
+            } => write!(f, "card {} {} vs {}", index + 1, ours, theirs),
+            Decider::Bid { ours, theirs } => write!(f, "bid {} vs {}", ours, theirs),
+            Decider::Tie => write!(f, "tie"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct Explanation {
+    pub hand: Hand,
+    /// 1-based position from the weakest hand
+    pub position: usize,
+    pub winnings: u64,
+    pub substituted: String,
+    /// Groups of the substituted hand
+    pub groups: Vec<(char, usize)>,
+    /// Comparison with the next weaker hand
+    pub below: Option<Decider>,
+    /// Comparison with the next stronger hand
+    pub above: Option<Decider>,
+}
+
+/// A width given to the formatter pads the `hand` and `as` columns to it.
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = f.width().unwrap_or(0);
+        let groups = self
+            .groups
+            .iter()
+            .map(|(c, g)| format!("{}x{}", g, c))
+            .join(" ");
+        let below = self
+            .below
+            .map(|d| d.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        write!(
+            f,
+            "{:>5} {:<width$} -> {:<width$} {:<12} {:<19} {:>5} {:>8}  {}",
+            self.position,
+            self.hand.cards,
+            self.substituted,
+            format!("{:?}", self.hand.rank),
+            groups,
+            self.hand.bid,
+            self.winnings,
+            below
+        )
+    }
+}
+
+/// The explanations as a table, the hand columns as wide as the longest hand.
+pub fn format_table(explanations: &[Explanation]) -> String {
+    let width = explanations
+        .iter()
+        .map(|e| e.hand.cards.chars().count())
+        .chain(["hand".len()])
+        .max()
+        .unwrap();
+    let mut table = format!(
+        "{:>5} {:<width$}    {:<width$} {:<12} {:<19} {:>5} {:>8}  {}\n",
+        "pos", "hand", "as", "rank", "groups", "bid", "winnings", "beats previous by"
+    );
+    explanations
+        .iter()
+        .for_each(|e| table.push_str(&format!("{:width$}\n", e)));
+    table
+}
+
+/// Output of the `--explain` and `--distribution` flags among `args`.
+pub fn report(ruleset: &Ruleset, input: &str, args: &[String]) -> String {
+    let mut out = String::new();
+    if args.iter().any(|a| a == "--explain") {
+        out.push_str(&format_table(&ruleset.explain(input)));
+    }
+    if args.iter().any(|a| a == "--distribution") {
+        let bids = input
+            .lines()
+            .map(|l| ruleset.process_hand(l).bid)
+            .collect::<Vec<_>>();
+        out.push_str(&rank_distribution(ruleset).to_string());
+        out.push_str(&format!(
+            "expected winnings: {:.1}\n",
+            expected_winnings(ruleset, &bids)
+        ));
+    }
+    out
+}
+
+pub fn calc_total_winnings(mut hands: Vec<Hand>) -> u64 {
+    hands.sort();
+
+    hands
+        .into_iter()
+        .enumerate()
+        .map(|(i, h)| (i + 1) as u64 * h.bid as u64)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rulesets() {
+        let jokers = Ruleset::jokers();
+        assert_eq!(jokers.evaluate("KTJJT"), Rank::FourOfAKind);
+        assert_eq!(jokers.evaluate("JJJJJ"), Rank::FiveOfAKind);
+        assert_eq!(Ruleset::camel_cards().evaluate("KTJJT"), Rank::TwoPair);
+
+        let two_jokers = Ruleset::new("J*23456789TQKA", "J*", 5, TieBreak::FirstCard);
+        assert_eq!(two_jokers.evaluate("2J*34"), Rank::ThreeOfAKind);
+        assert!(two_jokers.hand("*2345", 1) > two_jokers.hand("J2345", 1));
+
+        let seven = Ruleset::new("23456789TJQKA", "", 7, TieBreak::HighestCard);
+        assert_eq!(seven.evaluate("2233444"), Rank::FullHouse);
+        assert!(seven.hand("2345678", 1) < seven.hand("A234567", 1));
+
+        let by_rank = Ruleset::new("23456789TJQKA", "", 5, TieBreak::None);
+        let (low, high) = (by_rank.hand("AKQJ2", 5), by_rank.hand("23456", 7));
+        assert!(low < high);
+        assert_eq!(
+            by_rank.decide(&high, &low),
+            Decider::Bid { ours: 7, theirs: 5 }
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
+        let jokers = Ruleset::jokers();
+        let explanations = jokers.explain(input);
+
+        assert_eq!(explanations.iter().map(|e| e.winnings).sum::<u64>(), 5905);
+        let strongest = explanations.last().unwrap();
+        assert_eq!(strongest.hand.cards, "KTJJT");
+        assert_eq!(strongest.substituted, "KTTTT");
+        assert_eq!(strongest.groups, vec![('T', 4), ('K', 1)]);
+        assert_eq!(
+            strongest.below,
+            Some(Decider::Card {
+                index: 0,
+                ours: 'K',
+                theirs: 'Q'
+            })
+        );
+        assert_eq!(strongest.above, None);
+        assert_eq!(
+            explanations[1].below,
+            Some(Decider::Rank {
+                ours: Rank::TwoPair,
+                theirs: Rank::OnePair
+            })
+        );
+        assert_eq!(jokers.substitute("JJJJJ"), "AAAAA");
+        assert_eq!(format_table(&explanations).lines().count(), 6);
+
+        let seven = Ruleset::new("23456789TJQKA", "", 7, TieBreak::HighestCard);
+        let table = format_table(&seven.explain("2233444 10\nA234567 20"));
+        let lines = table.lines().collect::<Vec<_>>();
+        let rank_column = lines[0].find("rank");
+        assert_eq!(lines[1].find("HighCard"), rank_column);
+        assert_eq!(lines[2].find("FullHouse"), rank_column);
+
+        let args = ["--explain".to_string(), "--distribution".to_string()];
+        let out = report(&jokers, input, &args);
+        assert!(out.starts_with(&format_table(&explanations)));
+        assert!(out
+            .lines()
+            .last()
+            .unwrap()
+            .starts_with("expected winnings: "));
+        assert_eq!(report(&jokers, input, &[]), "");
+    }
+}
@@ -1,100 +1,45 @@
-use std::collections::HashMap;
-
-use itertools::{FoldWhile, Itertools};
-use nom::{
-    bytes::complete::{is_a, tag, take_while1},
-    character::{
-        complete::{char, line_ending},
-        is_alphanumeric,
-    },
-    combinator::map,
-    multi::{count, separated_list1},
-    sequence::{delimited, separated_pair},
-    IResult,
-};
-use num::integer;
+use day_08::Network;
 
 fn main() {
-    println!("{}", part2(include_str!("./input.txt")));
-}
-
-fn part2(input: &str) -> usize {
-    let (directions, nodes) = separated_pair(
-        map(is_a("RL"), |s: &str| {
-            s.chars()
-                .map(|c| match c {
-                    'R' => Direction::Right,
-                    'L' => Direction::Left,
-                    _ => unreachable!(),
-                })
-                .collect::<Vec<_>>()
-        }),
-        count(line_ending, 2),
-        separated_list1(line_ending, node_parser),
-    )(input)
-    .expect("invalid input")
-    .1;
-
-    let nodes = nodes.into_iter().collect::<HashMap<&str, (&str, &str)>>();
-
-    let starting_nodes = nodes
-        .iter()
-        .filter(|(n, (_, _))| n.ends_with('A'))
-        .map(|(n, (_, _))| *n)
-        .collect::<Vec<_>>();
-
-    starting_nodes
-        .into_iter()
-        .map(|n| find_steps_to_goal(n, &nodes, &directions))
-        .fold(1_usize, integer::lcm)
-}
-
-fn find_steps_to_goal(
-    starting_node: &str,
-    nodes: &HashMap<&str, (&str, &str)>,
-    directions: &[Direction],
-) -> usize {
-    match directions.iter().cycle().enumerate().fold_while(
-        (starting_node, 0),
-        |(acc, _), (i, d)| {
-            let (l, r) = nodes.get(acc).unwrap();
-            let next = match d {
-                Direction::Right => r,
-                Direction::Left => l,
-            };
-
-            if next.ends_with('Z') {
-                FoldWhile::Done((next, i))
-            } else {
-                FoldWhile::Continue((next, i))
-            }
-        },
-    ) {
-        FoldWhile::Done((_, i)) => i + 1,
-        FoldWhile::Continue(_) => panic!("invalid input"),
+    let input = include_str!("./input.txt");
+    let args = std::env::args().collect::<Vec<_>>();
+    let network = Network::parse(input);
+    if args.iter().any(|a| a == "--dot") {
+        print!("{}", network.to_dot());
+        return;
+    }
+    if args.iter().any(|a| a == "--json") {
+        println!("{}", network.to_json());
+        return;
     }
+    if args.iter().any(|a| a == "--analyze") {
+        let reachable = network.reachable_sets();
+        for (start, cycle) in network.ghost_cycles() {
+            println!(
+                "{}: reaches {} nodes, cycle after {} steps with period {}, goals at {:?} then {:?}",
+                start,
+                reachable[start].len(),
+                cycle.offset,
+                cycle.period,
+                cycle.pre_cycle_hits,
+                cycle.cycle_hits
+            );
+        }
+        println!(
+            "{} strongly connected components, {} unreachable nodes",
+            network.strongly_connected_components().len(),
+            network.unreachable_nodes().len()
+        );
+    }
+    println!("{}", part2(input));
 }
 
-fn node_parser(i: &str) -> IResult<&str, (&str, (&str, &str))> {
-    separated_pair(
-        take_while1(|c| is_alphanumeric(c as u8)),
-        tag(" = "),
-        delimited(
-            char('('),
-            separated_pair(
-                take_while1(|c| is_alphanumeric(c as u8)),
-                tag(", "),
-                take_while1(|c| is_alphanumeric(c as u8)),
-            ),
-            char(')'),
-        ),
-    )(i)
-}
+fn part2(input: &str) -> u128 {
+    let network = Network::parse(input);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    Right,
-    Left,
+    network
+        .steps_to_all(&network.starting_nodes(), |n| n.ends_with('Z'))
+        .expect("ghosts never arrive at the same time")
 }
 
 #[cfg(test)]
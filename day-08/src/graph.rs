@@ -0,0 +1,155 @@
+use crate::{Direction, GhostCycle, Network};
+use pathfinding::prelude::{bfs_reach, strongly_connected_components};
+use std::collections::{BTreeMap, BTreeSet};
+
+impl<'a> Network<'a> {
+    fn sorted_nodes(&self) -> Vec<&'a str> {
+        let mut nodes = self.nodes.keys().copied().collect::<Vec<_>>();
+        nodes.sort_unstable();
+        nodes
+    }
+
+    fn successors(&self, node: &str) -> Vec<&'a str> {
+        self.nodes
+            .get(node)
+            .map(|(l, r)| if l == r { vec![*l] } else { vec![*l, *r] })
+            .unwrap_or_default()
+    }
+
+    /// Graphviz graph of the network with start nodes in green and goal nodes in red.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph network {\n");
+        for node in self.sorted_nodes() {
+            let (l, r) = self.nodes[node];
+            if is_start(node) {
+                dot.push_str(&format!(
+                    "    \"{}\" [style=filled, fillcolor=green];\n",
+                    node
+                ));
+            } else if is_goal(node) {
+                dot.push_str(&format!(
+                    "    \"{}\" [style=filled, fillcolor=red];\n",
+                    node
+                ));
+            }
+            if l == r {
+                dot.push_str(&format!("    \"{}\" -> \"{}\" [label=\"LR\"];\n", node, l));
+            } else {
+                dot.push_str(&format!("    \"{}\" -> \"{}\" [label=\"L\"];\n", node, l));
+                dot.push_str(&format!("    \"{}\" -> \"{}\" [label=\"R\"];\n", node, r));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn to_json(&self) -> String {
+        let directions = self
+            .directions
+            .iter()
+            .map(|d| match d {
+                Direction::Right => 'R',
+                Direction::Left => 'L',
+            })
+            .collect::<String>();
+        let nodes = self
+            .sorted_nodes()
+            .into_iter()
+            .map(|node| {
+                let (l, r) = self.nodes[node];
+                format!(
+                    "{{\"name\":\"{}\",\"left\":\"{}\",\"right\":\"{}\",\"start\":{},\"goal\":{}}}",
+                    node,
+                    l,
+                    r,
+                    is_start(node),
+                    is_goal(node)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            "{{\"directions\":\"{}\",\"nodes\":[{}]}}",
+            directions, nodes
+        )
+    }
+
+    /// Nodes reachable from `start` by any choice of directions, including `start`.
+    pub fn reachable_from(&self, start: &'a str) -> BTreeSet<&'a str> {
+        bfs_reach(start, |n| self.successors(n)).collect()
+    }
+
+    pub fn reachable_sets(&self) -> BTreeMap<&'a str, BTreeSet<&'a str>> {
+        self.starting_nodes()
+            .into_iter()
+            .map(|s| (s, self.reachable_from(s)))
+            .collect()
+    }
+
+    /// Nodes no start node can reach.
+    pub fn unreachable_nodes(&self) -> BTreeSet<&'a str> {
+        let reachable = self
+            .reachable_sets()
+            .into_values()
+            .flatten()
+            .collect::<BTreeSet<_>>();
+        self.sorted_nodes()
+            .into_iter()
+            .filter(|n| !reachable.contains(n))
+            .collect()
+    }
+
+    pub fn strongly_connected_components(&self) -> Vec<Vec<&'a str>> {
+        let mut components =
+            strongly_connected_components(&self.sorted_nodes(), |n| self.successors(n));
+        components.iter_mut().for_each(|c| c.sort_unstable());
+        components.sort_unstable();
+        components
+    }
+
+    /// Cycle of every ghost when following the instructions towards `..Z` nodes.
+    pub fn ghost_cycles(&self) -> Vec<(&'a str, GhostCycle)> {
+        self.starting_nodes()
+            .into_iter()
+            .map(|s| (s, self.cycle(s, is_goal)))
+            .collect()
+    }
+}
+
+fn is_start(node: &str) -> bool {
+    node.ends_with('A')
+}
+
+fn is_goal(node: &str) -> bool {
+    node.ends_with('Z')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analysis() {
+        let network = Network::parse(
+            "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)\nYYY = (XXX, 11A)",
+        );
+
+        assert_eq!(network.reachable_from("11A").len(), 4);
+        assert_eq!(network.unreachable_nodes(), BTreeSet::from(["YYY"]));
+        let components = network.strongly_connected_components();
+        assert!(components.contains(&vec!["11B", "11Z"]));
+        assert!(components.contains(&vec!["22B", "22C", "22Z"]));
+        assert_eq!(components.len(), 6);
+
+        let cycles = network.ghost_cycles();
+        assert_eq!(cycles[0].1.period, 2);
+        assert_eq!(cycles[1].1.cycle_hits, vec![3, 6]);
+
+        let dot = network.to_dot();
+        assert!(dot.contains("\"11A\" [style=filled, fillcolor=green];"));
+        assert!(dot.contains("\"22B\" -> \"22C\" [label=\"LR\"];"));
+        assert!(network.to_json().starts_with(
+            "{\"directions\":\"LR\",\"nodes\":[{\"name\":\"11A\",\"left\":\"11B\",\"right\":\"XXX\",\"start\":true,\"goal\":false}"
+        ));
+    }
+}
@@ -0,0 +1,240 @@
+use nom::{
+    bytes::complete::{is_a, tag, take_while1},
+    character::{
+        complete::{char, line_ending},
+        is_alphanumeric,
+    },
+    combinator::map,
+    multi::{count, separated_list1},
+    sequence::{delimited, separated_pair},
+    IResult,
+};
+use num::Integer;
+use std::collections::HashMap;
+
+mod graph;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Right,
+    Left,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network<'a> {
+    pub directions: Vec<Direction>,
+    pub nodes: HashMap<&'a str, (&'a str, &'a str)>,
+}
+
+impl<'a> Network<'a> {
+    pub fn parse(input: &'a str) -> Self {
+        let (directions, nodes) = separated_pair(
+            map(is_a("RL"), |s: &str| {
+                s.chars()
+                    .map(|c| match c {
+                        'R' => Direction::Right,
+                        'L' => Direction::Left,
+                        _ => unreachable!(),
+                    })
+                    .collect::<Vec<_>>()
+            }),
+            count(line_ending, 2),
+            separated_list1(line_ending, node_parser),
+        )(input)
+        .expect("invalid input")
+        .1;
+
+        Self {
+            directions,
+            nodes: nodes.into_iter().collect(),
+        }
+    }
+
+    /// Node reached from `node` when following the direction at `index`.
+    pub fn step(&self, node: &str, index: usize) -> &'a str {
+        let (l, r) = self
+            .nodes
+            .get(node)
+            .unwrap_or_else(|| panic!("unknown node: {}", node));
+        match self.directions[index % self.directions.len()] {
+            Direction::Right => r,
+            Direction::Left => l,
+        }
+    }
+
+    /// Walks from `start` until a (node, direction index) state repeats.
+    pub fn cycle(&self, start: &'a str, is_goal: impl Fn(&str) -> bool) -> GhostCycle {
+        let mut seen = HashMap::new();
+        let mut hits = Vec::new();
+        let mut node = start;
+        let mut steps = 0;
+
+        loop {
+            let index = steps as usize % self.directions.len();
+            if let Some(first) = seen.insert((node, index), steps) {
+                let (pre_cycle_hits, cycle_hits) = hits.into_iter().partition(|h| *h < first);
+                return GhostCycle {
+                    offset: first,
+                    period: steps - first,
+                    pre_cycle_hits,
+                    cycle_hits,
+                };
+            }
+            if is_goal(node) {
+                hits.push(steps);
+            }
+            node = self.step(node, index);
+            steps += 1;
+        }
+    }
+
+    /// First step count at which every ghost stands on a goal node at once.
+    pub fn steps_to_all(&self, starts: &[&'a str], is_goal: impl Fn(&str) -> bool) -> Option<u128> {
+        let cycles = starts
+            .iter()
+            .map(|s| self.cycle(s, &is_goal))
+            .collect::<Vec<_>>();
+        first_common_hit(&cycles)
+    }
+
+    pub fn starting_nodes(&self) -> Vec<&'a str> {
+        let mut starts = self
+            .nodes
+            .keys()
+            .filter(|n| n.ends_with('A'))
+            .copied()
+            .collect::<Vec<_>>();
+        starts.sort_unstable();
+        starts
+    }
+}
+
+impl<'a> From<&'a str> for Network<'a> {
+    fn from(s: &'a str) -> Self {
+        Self::parse(s)
+    }
+}
+
+/// Goal visits of a single ghost. Hits in the cycle repeat every `period` steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle {
+    /// Steps before the ghost enters its cycle
+    pub offset: u64,
+    pub period: u64,
+    pub pre_cycle_hits: Vec<u64>,
+    pub cycle_hits: Vec<u64>,
+}
+
+impl GhostCycle {
+    pub fn is_hit(&self, steps: u128) -> bool {
+        if steps < self.offset as u128 {
+            self.pre_cycle_hits.contains(&(steps as u64))
+        } else {
+            let in_cycle = (steps - self.offset as u128) % self.period as u128;
+            self.cycle_hits.contains(&(self.offset + in_cycle as u64))
+        }
+    }
+}
+
+/// Smallest step count hit by every cycle, combining the cycles with generalized CRT.
+pub fn first_common_hit(cycles: &[GhostCycle]) -> Option<u128> {
+    let latest = cycles.iter().max_by_key(|c| c.offset)?;
+
+    // Before every ghost is in its cycle, only the pre-cycle hits of the last one can match.
+    if let Some(hit) = latest
+        .pre_cycle_hits
+        .iter()
+        .map(|h| *h as u128)
+        .find(|h| cycles.iter().all(|c| c.is_hit(*h)))
+    {
+        return Some(hit);
+    }
+
+    let residues = cycles.iter().fold(vec![(0_u128, 1_u128)], |acc, c| {
+        acc.iter()
+            .flat_map(|(r, m)| {
+                c.cycle_hits.iter().filter_map(move |h| {
+                    crt(*r, *m, *h as u128 % c.period as u128, c.period as u128)
+                })
+            })
+            .collect()
+    });
+
+    let offset = latest.offset as u128;
+    residues
+        .into_iter()
+        .map(|(r, m)| {
+            if r >= offset {
+                r
+            } else {
+                r + (offset - r).div_ceil(m) * m
+            }
+        })
+        .min()
+}
+
+/// Combines `x = r1 mod m1` and `x = r2 mod m2` into `x = r mod lcm(m1, m2)`, if solvable.
+fn crt(r1: u128, m1: u128, r2: u128, m2: u128) -> Option<(u128, u128)> {
+    let gcd = (m1 as i128).extended_gcd(&(m2 as i128));
+    let diff = r2 as i128 - r1 as i128;
+    if diff % gcd.gcd != 0 {
+        return None;
+    }
+    let lcm = m1 / gcd.gcd as u128 * m2;
+    let step = (m2 as i128) / gcd.gcd;
+    let k = (diff / gcd.gcd * gcd.x).rem_euclid(step);
+    Some((
+        (r1 as i128 + m1 as i128 * k).rem_euclid(lcm as i128) as u128,
+        lcm,
+    ))
+}
+
+fn node_parser(i: &str) -> IResult<&str, (&str, (&str, &str))> {
+    separated_pair(
+        take_while1(|c| is_alphanumeric(c as u8)),
+        tag(" = "),
+        delimited(
+            char('('),
+            separated_pair(
+                take_while1(|c| is_alphanumeric(c as u8)),
+                tag(", "),
+                take_while1(|c| is_alphanumeric(c as u8)),
+            ),
+            char(')'),
+        ),
+    )(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycles() {
+        let network = Network::parse(
+            "L\n\n11A = (11Z, 11Z)\n11Z = (11B, 11B)\n11B = (11Z, 11Z)\n22A = (22B, 22B)\n22B = (22Z, 22Z)\n22Z = (22C, 22C)\n22C = (22Z, 22Z)\n44A = (44Z, 44Z)\n44Z = (44B, 44B)\n44B = (44C, 44C)\n44C = (44B, 44B)",
+        );
+        let is_goal = |n: &str| n.ends_with('Z');
+
+        assert_eq!(
+            network.cycle("44A", is_goal),
+            GhostCycle {
+                offset: 2,
+                period: 2,
+                pre_cycle_hits: vec![1],
+                cycle_hits: vec![]
+            }
+        );
+        assert_eq!(network.steps_to_all(&["11A", "22A"], is_goal), None);
+        assert_eq!(network.steps_to_all(&["11A", "44A"], is_goal), Some(1));
+        assert_eq!(network.steps_to_all(&["22A", "44A"], is_goal), None);
+        assert_eq!(network.steps_to_all(&["22A"], is_goal), Some(2));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(1, 4, 3, 6), Some((9, 12)));
+        assert_eq!(crt(0, 4, 1, 6), None);
+        assert_eq!(crt(2, 3, 3, 5), Some((8, 15)));
+    }
+}
@@ -1,4 +1,4 @@
-use itertools::Itertools;
+use day_09::Sequence;
 
 fn main() {
     println!("{}", part2(include_str!("./input.txt")));
@@ -9,28 +9,9 @@ fn part2(input: &str) -> i64 {
 }
 
 fn line(input: &str) -> i64 {
-    let nums = input
-        .split_ascii_whitespace()
-        .map(|s| s.parse::<i64>().expect("invalid digit"))
-        .collect::<Vec<_>>();
+    let sequence = input.parse::<Sequence>().expect("invalid input");
 
-    find_previous(&nums)
-}
-
-fn find_previous(line: &[i64]) -> i64 {
-    let differences = line
-        .iter()
-        .tuple_windows::<(_, _)>()
-        .map(|(a, b)| b - a)
-        .collect::<Vec<_>>();
-
-    let first = *line.first().expect("invalid input");
-
-    if differences.iter().all(|d| *d == 0) {
-        first
-    } else {
-        first - find_previous(&differences)
-    }
+    sequence.at(-1).try_into().expect("value out of range")
 }
 
 #[cfg(test)]
@@ -57,5 +38,6 @@ mod tests {
         assert_eq!(line("0 3 6 9 12 15"), -3);
         assert_eq!(line("1 3 6 10 15 21"), 0);
         assert_eq!(line("10  13  16  21  30  45"), 5);
+        assert_eq!(line("1 2 4 8 16"), 1);
     }
 }
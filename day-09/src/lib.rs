@@ -0,0 +1,142 @@
+use itertools::Itertools;
+use num::{BigInt, Zero};
+use std::{error::Error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceError {
+    Empty,
+    InvalidReading(String),
+}
+
+impl fmt::Display for SequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SequenceError::Empty => write!(f, "no readings"),
+            SequenceError::InvalidReading(s) => write!(f, "invalid reading: {}", s),
+        }
+    }
+}
+
+impl Error for SequenceError {}
+
+/// The minimal-degree polynomial through readings taken at indices `0, 1, 2, ...`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    /// Leading forward differences `Δ^k f(0)` up to the degree
+    newton: Vec<BigInt>,
+    /// Whether a row of differences became all zeros within the readings
+    verified: bool,
+}
+
+impl Sequence {
+    /// Fits a polynomial of degree at most one less than the number of readings.
+    ///
+    /// The fit is only verified if a row of differences becomes all zeros before the
+    /// readings run out, so a single reading or `1 2` never is.
+    pub fn fit(readings: &[BigInt]) -> Result<Self, SequenceError> {
+        if readings.is_empty() {
+            return Err(SequenceError::Empty);
+        }
+
+        let mut newton = Vec::new();
+        let mut row = readings.to_vec();
+        loop {
+            newton.push(row[0].clone());
+            row = row
+                .iter()
+                .tuple_windows()
+                .map(|(a, b)| b - a)
+                .collect::<Vec<_>>();
+            if row.is_empty() {
+                return Ok(Self {
+                    newton,
+                    verified: false,
+                });
+            }
+            if row.iter().all(Zero::is_zero) {
+                return Ok(Self {
+                    newton,
+                    verified: true,
+                });
+            }
+        }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.newton.len() - 1
+    }
+
+    /// Whether the readings show the degree, rather than just bounding it.
+    pub fn is_verified(&self) -> bool {
+        self.verified
+    }
+
+    /// Value of the polynomial at any index, including negative ones.
+    pub fn at(&self, index: impl Into<BigInt>) -> BigInt {
+        let index = index.into();
+        let mut binomial = BigInt::from(1);
+        let mut value = BigInt::zero();
+        for (k, coefficient) in self.newton.iter().enumerate() {
+            value += coefficient * &binomial;
+            binomial = binomial * (&index - k) / (k + 1);
+        }
+        value
+    }
+}
+
+impl FromStr for Sequence {
+    type Err = SequenceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let readings = s
+            .split_ascii_whitespace()
+            .map(|r| {
+                r.parse::<BigInt>()
+                    .map_err(|_| SequenceError::InvalidReading(r.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::fit(&readings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence() {
+        let sequence = "10 13 16 21 30 45".parse::<Sequence>().unwrap();
+        assert_eq!(sequence.degree(), 3);
+        assert!(sequence.is_verified());
+        assert_eq!(sequence.at(6), BigInt::from(68));
+        assert_eq!(sequence.at(-1), BigInt::from(5));
+        assert_eq!(sequence.at(2), BigInt::from(16));
+
+        let squares = "0 1 4 9".parse::<Sequence>().unwrap();
+        assert_eq!(squares.degree(), 2);
+        assert_eq!(
+            squares.at(3_000_000_000_i64),
+            BigInt::from(9_000_000_000_000_000_000_i128)
+        );
+        assert_eq!(squares.at(-4), BigInt::from(16));
+
+        let constant = "7 7".parse::<Sequence>().unwrap();
+        assert_eq!(constant.degree(), 0);
+        assert!(constant.is_verified());
+        assert_eq!("".parse::<Sequence>(), Err(SequenceError::Empty));
+
+        let powers = "1 2 4 8 16".parse::<Sequence>().unwrap();
+        assert_eq!(powers.degree(), 4);
+        assert!(!powers.is_verified());
+        assert_eq!(powers.at(5), BigInt::from(31));
+        let line = "1 2".parse::<Sequence>().unwrap();
+        assert_eq!((line.degree(), line.is_verified()), (1, false));
+        let single = "5".parse::<Sequence>().unwrap();
+        assert_eq!((single.degree(), single.is_verified()), (0, false));
+        assert_eq!(single.at(-3), BigInt::from(5));
+        assert_eq!(
+            "1 x".parse::<Sequence>(),
+            Err(SequenceError::InvalidReading("x".to_string()))
+        );
+    }
+}
@@ -0,0 +1,405 @@
+use itertools::Itertools;
+use std::{collections::HashSet, error::Error, fmt};
+
+mod render;
+pub use render::{render_ppm, render_text};
+
+/// Side of a pipe, also used as the side a pipe is entered from.
+#[derive(Debug, PartialEq, Clone, Eq, Hash, Copy, PartialOrd, Ord)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Debug, PartialEq, Clone, Eq, Hash, Copy)]
+pub struct Pipe {
+    pub x: usize,
+    pub y: usize,
+    pub pipe_type: PipeType,
+}
+
+impl Pipe {
+    pub fn new(x: usize, y: usize, pipe_type: PipeType) -> Self {
+        Self { x, y, pipe_type }
+    }
+
+    pub fn successor<'a>(
+        &self,
+        entry_direction: Direction,
+        pipes: &'a [Vec<Pipe>],
+    ) -> Option<(Direction, &'a Self)> {
+        if entry_direction != Direction::Right && self.pipe_type.has_pos_x() {
+            self.get_pos_x(pipes).map(|p| (Direction::Left, p))
+        } else if entry_direction != Direction::Left && self.pipe_type.has_neg_x() {
+            self.get_neg_x(pipes).map(|p| (Direction::Right, p))
+        } else if entry_direction != Direction::Up && self.pipe_type.has_neg_y() {
+            self.get_neg_y(pipes).map(|p| (Direction::Down, p))
+        } else if entry_direction != Direction::Down && self.pipe_type.has_pos_y() {
+            self.get_pos_y(pipes).map(|p| (Direction::Up, p))
+        } else {
+            None
+        }
+    }
+
+    /// Connected pipe on the given side, together with the side it is entered from.
+    pub fn neighbour<'a>(
+        &self,
+        side: Direction,
+        pipes: &'a [Vec<Pipe>],
+    ) -> Option<(Direction, &'a Pipe)> {
+        match side {
+            Direction::Right => self.get_pos_x(pipes).map(|p| (Direction::Left, p)),
+            Direction::Left => self.get_neg_x(pipes).map(|p| (Direction::Right, p)),
+            Direction::Down => self.get_pos_y(pipes).map(|p| (Direction::Up, p)),
+            Direction::Up => self.get_neg_y(pipes).map(|p| (Direction::Down, p)),
+        }
+    }
+
+    pub fn get_pos_x<'a>(&self, pipes: &'a [Vec<Pipe>]) -> Option<&'a Pipe> {
+        if self.pipe_type.has_pos_x() {
+            pipes
+                .get(self.y)?
+                .get(self.x + 1)
+                .filter(|p| p.pipe_type.has_neg_x())
+        } else {
+            None
+        }
+    }
+    pub fn get_neg_x<'a>(&self, pipes: &'a [Vec<Pipe>]) -> Option<&'a Pipe> {
+        if self.pipe_type.has_neg_x() {
+            pipes
+                .get(self.y)?
+                .get(self.x.checked_sub(1)?)
+                .filter(|p| p.pipe_type.has_pos_x())
+        } else {
+            None
+        }
+    }
+    pub fn get_pos_y<'a>(&self, pipes: &'a [Vec<Pipe>]) -> Option<&'a Pipe> {
+        if self.pipe_type.has_pos_y() {
+            pipes
+                .get(self.y + 1)?
+                .get(self.x)
+                .filter(|p| p.pipe_type.has_neg_y())
+        } else {
+            None
+        }
+    }
+    pub fn get_neg_y<'a>(&self, pipes: &'a [Vec<Pipe>]) -> Option<&'a Pipe> {
+        if self.pipe_type.has_neg_y() {
+            pipes
+                .get(self.y.checked_sub(1)?)?
+                .get(self.x)
+                .filter(|p| p.pipe_type.has_pos_y())
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum PipeType {
+    Empty,
+    Starting,
+    Horizontal,
+    Vertical,
+    NorthEast,
+    NorthWest,
+    SouthWest,
+    SouthEast,
+}
+
+impl PipeType {
+    pub fn has_pos_x(&self) -> bool {
+        matches!(
+            self,
+            Self::Horizontal | Self::NorthEast | Self::SouthEast | Self::Starting
+        )
+    }
+    pub fn has_neg_x(&self) -> bool {
+        matches!(
+            self,
+            Self::Horizontal | Self::NorthWest | Self::SouthWest | Self::Starting
+        )
+    }
+    pub fn has_pos_y(&self) -> bool {
+        matches!(
+            self,
+            Self::Vertical | Self::SouthEast | Self::SouthWest | Self::Starting
+        )
+    }
+    pub fn has_neg_y(&self) -> bool {
+        matches!(
+            self,
+            Self::Vertical | Self::NorthEast | Self::NorthWest | Self::Starting
+        )
+    }
+
+    /// The pipe open to both given sides.
+    pub fn from_sides(a: Direction, b: Direction) -> Option<Self> {
+        match (a.min(b), a.max(b)) {
+            (Direction::Left, Direction::Right) => Some(Self::Horizontal),
+            (Direction::Up, Direction::Down) => Some(Self::Vertical),
+            (Direction::Right, Direction::Up) => Some(Self::NorthEast),
+            (Direction::Left, Direction::Up) => Some(Self::NorthWest),
+            (Direction::Left, Direction::Down) => Some(Self::SouthWest),
+            (Direction::Right, Direction::Down) => Some(Self::SouthEast),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<char> for PipeType {
+    type Error = ();
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Self::Empty),
+            'S' => Ok(Self::Starting),
+            '-' => Ok(Self::Horizontal),
+            '|' => Ok(Self::Vertical),
+            'L' => Ok(Self::NorthEast),
+            'J' => Ok(Self::NorthWest),
+            '7' => Ok(Self::SouthWest),
+            'F' => Ok(Self::SouthEast),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub enum MazeError {
+    InvalidCharacter(char),
+    NoStart,
+    /// No pair of pipes next to the start closes a loop
+    NoLoop,
+    /// More than one loop passes through the start
+    AmbiguousLoop(Vec<PipeType>),
+}
+
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MazeError::InvalidCharacter(c) => write!(f, "invalid pipe character: {}", c),
+            MazeError::NoStart => write!(f, "no starting pipe found"),
+            MazeError::NoLoop => write!(f, "no loop passes through the starting pipe"),
+            MazeError::AmbiguousLoop(types) => {
+                write!(f, "starting pipe could be any of {:?}", types)
+            }
+        }
+    }
+}
+
+impl Error for MazeError {}
+
+/// The main loop, with the tiles in walking order beginning at the start.
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct PipeLoop {
+    pub start_type: PipeType,
+    pub tiles: Vec<(usize, usize)>,
+}
+
+impl PipeLoop {
+    /// Type of a loop tile, with the start replaced by the pipe it stands for.
+    pub fn pipe_type(&self, maze: &Maze, (x, y): (usize, usize)) -> PipeType {
+        if (x, y) == maze.start {
+            self.start_type
+        } else {
+            maze.pipes[y][x].pipe_type
+        }
+    }
+
+    /// Bends of the loop in walking order, forming its polygon.
+    pub fn corners(&self, maze: &Maze) -> Vec<(usize, usize)> {
+        self.tiles
+            .iter()
+            .copied()
+            .filter(|t| {
+                !matches!(
+                    self.pipe_type(maze, *t),
+                    PipeType::Horizontal | PipeType::Vertical
+                )
+            })
+            .collect()
+    }
+
+    /// Area of the polygon through the tile centres, by the shoelace formula.
+    pub fn area(&self, maze: &Maze) -> usize {
+        let corners = self.corners(maze);
+        let twice = corners
+            .iter()
+            .circular_tuple_windows()
+            .map(|((x1, y1), (x2, y2))| (*x1 as i64 * *y2 as i64) - (*x2 as i64 * *y1 as i64))
+            .sum::<i64>();
+        twice.unsigned_abs() as usize / 2
+    }
+
+    /// Tiles inside the loop, by Pick's theorem: `A = i + b / 2 - 1`.
+    pub fn enclosed_count(&self, maze: &Maze) -> usize {
+        self.area(maze) + 1 - self.tiles.len() / 2
+    }
+
+    /// Coordinates of the tiles inside the loop, row by row.
+    pub fn enclosed_tiles(&self, maze: &Maze) -> Vec<(usize, usize)> {
+        let on_loop = self.tiles.iter().copied().collect::<HashSet<_>>();
+        let mut enclosed = Vec::new();
+        for y in 0..maze.height() {
+            let mut inside = false;
+            for x in 0..maze.width() {
+                if on_loop.contains(&(x, y)) {
+                    if self.pipe_type(maze, (x, y)).has_neg_y() {
+                        inside = !inside;
+                    }
+                } else if inside {
+                    enclosed.push((x, y));
+                }
+            }
+        }
+        enclosed
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct Maze {
+    pub pipes: Vec<Vec<Pipe>>,
+    pub start: (usize, usize),
+}
+
+impl Maze {
+    pub fn parse(input: &str) -> Result<Self, MazeError> {
+        let pipes = input
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.char_indices()
+                    .map(|(x, c)| {
+                        c.try_into()
+                            .map(|t| Pipe::new(x, y, t))
+                            .map_err(|_| MazeError::InvalidCharacter(c))
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<_>>, _>>()?;
+
+        let start = pipes
+            .iter()
+            .flatten()
+            .find(|p| p.pipe_type == PipeType::Starting)
+            .map(|p| (p.x, p.y))
+            .ok_or(MazeError::NoStart)?;
+
+        Ok(Self { pipes, start })
+    }
+
+    pub fn width(&self) -> usize {
+        self.pipes.first().map(|l| l.len()).unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.pipes.len()
+    }
+
+    fn starting_pipe(&self) -> &Pipe {
+        &self.pipes[self.start.1][self.start.0]
+    }
+
+    /// Follows the pipes leaving the start on `side`. Returns the visited tiles
+    /// and the side the start is entered from again, if the walk gets back there.
+    fn walk(&self, side: Direction) -> Option<(Vec<(usize, usize)>, Direction)> {
+        let start = self.starting_pipe();
+        let mut tiles = vec![self.start];
+        let mut current = start.neighbour(side, &self.pipes)?;
+
+        while tiles.len() <= self.width() * self.height() {
+            let (entry, pipe) = current;
+            if pipe == start {
+                return Some((tiles, entry));
+            }
+            tiles.push((pipe.x, pipe.y));
+            current = pipe.successor(entry, &self.pipes)?;
+        }
+
+        None
+    }
+
+    /// Finds the one loop through the start, ignoring pipes that lead elsewhere.
+    pub fn trace_loop(&self) -> Result<PipeLoop, MazeError> {
+        let mut loops: Vec<PipeLoop> = Vec::new();
+
+        for side in [
+            Direction::Right,
+            Direction::Left,
+            Direction::Down,
+            Direction::Up,
+        ] {
+            if let Some((tiles, entry)) = self.walk(side) {
+                let Some(start_type) = PipeType::from_sides(side, entry) else {
+                    continue;
+                };
+                if !loops.iter().any(|l| l.start_type == start_type) {
+                    loops.push(PipeLoop { start_type, tiles });
+                }
+            }
+        }
+
+        match loops.len() {
+            0 => Err(MazeError::NoLoop),
+            1 => Ok(loops.remove(0)),
+            _ => Err(MazeError::AmbiguousLoop(
+                loops.into_iter().map(|l| l.start_type).collect(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_loop() {
+        let maze = Maze::parse("-S-7.\n.|.|.\n.L-J.").unwrap();
+        let pipe_loop = maze.trace_loop().unwrap();
+        assert_eq!(pipe_loop.start_type, PipeType::SouthEast);
+        assert_eq!(pipe_loop.tiles.len(), 8);
+        assert_eq!(pipe_loop.tiles[..2], [(1, 0), (2, 0)]);
+
+        assert_eq!(
+            pipe_loop.corners(&maze),
+            vec![(1, 0), (3, 0), (3, 2), (1, 2)]
+        );
+        assert_eq!(pipe_loop.area(&maze), 4);
+        assert_eq!(pipe_loop.enclosed_count(&maze), 1);
+        assert_eq!(pipe_loop.enclosed_tiles(&maze), vec![(2, 1)]);
+
+        assert_eq!(
+            Maze::parse("F7.\nLS7\n.LJ").unwrap().trace_loop(),
+            Err(MazeError::AmbiguousLoop(vec![
+                PipeType::SouthEast,
+                PipeType::NorthWest
+            ]))
+        );
+        assert_eq!(
+            Maze::parse("S-.").unwrap().trace_loop(),
+            Err(MazeError::NoLoop)
+        );
+        assert_eq!(Maze::parse("..").unwrap_err(), MazeError::NoStart);
+        assert_eq!(
+            Maze::parse("S.x").unwrap_err(),
+            MazeError::InvalidCharacter('x')
+        );
+    }
+
+    #[test]
+    fn test_enclosed_tiles() {
+        let maze = Maze::parse(
+            "FF7FSF7F7F7F7F7F---7\nL|LJ||||||||||||F--J\nFL-7LJLJ||||||LJL-77\nF--JF--7||LJLJ7F7FJ-\nL---JF-JLJ.||-FJLJJ7\n|F|F-JF---7F7-L7L|7|\n|FFJF7L7F-JF7|JL---7\n7-L-JL7||F7|L7F-7F7|\nL.L7LFJ|||||FJL7||LJ\nL7JLJL-JLJLJL--JLJ.L",
+        )
+        .unwrap();
+        let pipe_loop = maze.trace_loop().unwrap();
+        assert_eq!(pipe_loop.enclosed_count(&maze), 10);
+        assert_eq!(pipe_loop.enclosed_tiles(&maze).len(), 10);
+    }
+}
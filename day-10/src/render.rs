@@ -0,0 +1,156 @@
+use crate::{Maze, PipeLoop, PipeType};
+use std::collections::HashSet;
+
+const RESET: &str = "\x1b[0m";
+const LOOP: &str = "\x1b[1;33m";
+const START: &str = "\x1b[1;31m";
+const JUNK: &str = "\x1b[2m";
+const INSIDE: &str = "\x1b[42m";
+
+type Rgb = [u8; 3];
+const LOOP_RGB: Rgb = [255, 220, 60];
+const START_RGB: Rgb = [230, 40, 40];
+const JUNK_RGB: Rgb = [110, 110, 110];
+const INSIDE_RGB: Rgb = [40, 140, 60];
+const OUTSIDE_RGB: Rgb = [20, 20, 30];
+
+impl PipeType {
+    pub fn box_char(&self) -> char {
+        match self {
+            PipeType::Empty => ' ',
+            PipeType::Starting => 'S',
+            PipeType::Horizontal => '─',
+            PipeType::Vertical => '│',
+            PipeType::NorthEast => '└',
+            PipeType::NorthWest => '┘',
+            PipeType::SouthWest => '┐',
+            PipeType::SouthEast => '┌',
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+enum Tile {
+    Loop(PipeType),
+    Start(PipeType),
+    Inside(PipeType),
+    Outside(PipeType),
+}
+
+fn classify(maze: &Maze, pipe_loop: &PipeLoop) -> Vec<Vec<Tile>> {
+    let on_loop = pipe_loop.tiles.iter().copied().collect::<HashSet<_>>();
+    let inside = pipe_loop
+        .enclosed_tiles(maze)
+        .into_iter()
+        .collect::<HashSet<_>>();
+
+    maze.pipes
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|p| {
+                    let pos = (p.x, p.y);
+                    if pos == maze.start {
+                        Tile::Start(pipe_loop.start_type)
+                    } else if on_loop.contains(&pos) {
+                        Tile::Loop(p.pipe_type)
+                    } else if inside.contains(&pos) {
+                        Tile::Inside(p.pipe_type)
+                    } else {
+                        Tile::Outside(p.pipe_type)
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Draws the loop with box-drawing characters. With `colour`, junk pipes are
+/// dimmed and inside tiles get a green background; without it, tiles off the
+/// loop are drawn as `I` or `O`.
+pub fn render_text(maze: &Maze, pipe_loop: &PipeLoop, colour: bool) -> String {
+    classify(maze, pipe_loop)
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|tile| match (tile, colour) {
+                    (Tile::Loop(t), false) | (Tile::Start(t), false) => t.box_char().to_string(),
+                    (Tile::Inside(_), false) => "I".to_string(),
+                    (Tile::Outside(_), false) => "O".to_string(),
+                    (Tile::Loop(t), true) => format!("{}{}{}", LOOP, t.box_char(), RESET),
+                    (Tile::Start(t), true) => format!("{}{}{}", START, t.box_char(), RESET),
+                    (Tile::Inside(t), true) => {
+                        format!("{}{}{}{}", INSIDE, JUNK, t.box_char(), RESET)
+                    }
+                    (Tile::Outside(t), true) => format!("{}{}{}", JUNK, t.box_char(), RESET),
+                })
+                .collect::<String>()
+        })
+        .map(|row| row + "\n")
+        .collect()
+}
+
+/// Binary PPM image of the maze with every tile drawn as a `scale`×`scale` block.
+pub fn render_ppm(maze: &Maze, pipe_loop: &PipeLoop, scale: usize) -> Vec<u8> {
+    let scale = scale.max(3);
+    let width = maze.width() * scale;
+    let height = maze.height() * scale;
+    let mut pixels = vec![OUTSIDE_RGB; width * height];
+
+    for (y, row) in classify(maze, pipe_loop).into_iter().enumerate() {
+        for (x, tile) in row.into_iter().enumerate() {
+            let (pipe_type, colour, background) = match tile {
+                Tile::Loop(t) => (t, LOOP_RGB, OUTSIDE_RGB),
+                Tile::Start(t) => (t, START_RGB, OUTSIDE_RGB),
+                Tile::Inside(t) => (t, JUNK_RGB, INSIDE_RGB),
+                Tile::Outside(t) => (t, JUNK_RGB, OUTSIDE_RGB),
+            };
+            for dy in 0..scale {
+                for dx in 0..scale {
+                    let drawn = pipe_pixel(pipe_type, dx, dy, scale);
+                    pixels[(y * scale + dy) * width + x * scale + dx] =
+                        if drawn { colour } else { background };
+                }
+            }
+        }
+    }
+
+    let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    ppm.extend(pixels.into_iter().flatten());
+    ppm
+}
+
+/// Whether the pixel at `dx`, `dy` of a tile belongs to the drawn pipe.
+fn pipe_pixel(pipe_type: PipeType, dx: usize, dy: usize, scale: usize) -> bool {
+    let mid = scale / 2;
+    if pipe_type == PipeType::Empty {
+        return false;
+    }
+    (dx == mid && dy == mid)
+        || (dy == mid && dx > mid && pipe_type.has_pos_x())
+        || (dy == mid && dx < mid && pipe_type.has_neg_x())
+        || (dx == mid && dy > mid && pipe_type.has_pos_y())
+        || (dx == mid && dy < mid && pipe_type.has_neg_y())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let maze = Maze::parse("-S-7.\n.|.|.\n.L-J.").unwrap();
+        let pipe_loop = maze.trace_loop().unwrap();
+
+        assert_eq!(
+            render_text(&maze, &pipe_loop, false),
+            "O┌─┐O\nO│I│O\nO└─┘O\n"
+        );
+        assert!(render_text(&maze, &pipe_loop, true).contains(&format!("{}─{}", JUNK, RESET)));
+
+        let ppm = render_ppm(&maze, &pipe_loop, 3);
+        let header = "P6\n15 9\n255\n";
+        assert!(ppm.starts_with(header.as_bytes()));
+        assert_eq!(ppm.len(), header.len() + 15 * 9 * 3);
+    }
+}
@@ -0,0 +1,279 @@
+use itertools::Itertools;
+use num::{FromPrimitive, PrimInt};
+use std::{cmp::Reverse, collections::BTreeMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Space {
+    Empty,
+    Galaxy,
+}
+
+impl TryFrom<char> for Space {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Empty),
+            '#' => Ok(Self::Galaxy),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    /// Galaxy positions as `(x, y)`, row by row
+    pub galaxies: Vec<(usize, usize)>,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Image {
+    pub fn parse(input: &str) -> Self {
+        let rows = input
+            .lines()
+            .map(|l| {
+                l.chars()
+                    .map(|c| Space::try_from(c).expect("invalid char"))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let galaxies = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter_map(move |(x, s)| (*s == Space::Galaxy).then_some((x, y)))
+            })
+            .collect();
+
+        Self {
+            galaxies,
+            width: rows.first().map_or(0, Vec::len),
+            height: rows.len(),
+        }
+    }
+
+    fn occupied_columns(&self) -> Vec<bool> {
+        let mut occupied = vec![false; self.width];
+        self.galaxies.iter().for_each(|(x, _)| occupied[*x] = true);
+        occupied
+    }
+
+    fn occupied_rows(&self) -> Vec<bool> {
+        let mut occupied = vec![false; self.height];
+        self.galaxies.iter().for_each(|(_, y)| occupied[*y] = true);
+        occupied
+    }
+
+    /// Galaxy positions after every empty row and column grew to `factor` lines.
+    pub fn expanded<T: PrimInt + FromPrimitive>(&self, factor: T) -> Vec<(T, T)> {
+        self.expand(&Expansion::Constant(factor), &Expansion::Constant(factor))
+            .galaxies
+    }
+
+    /// Expands empty columns by `columns` and empty rows by `rows`.
+    pub fn expand<T: PrimInt + FromPrimitive>(
+        &self,
+        columns: &Expansion<T>,
+        rows: &Expansion<T>,
+    ) -> Universe<T> {
+        let xs = line_offsets(&self.occupied_columns(), columns);
+        let ys = line_offsets(&self.occupied_rows(), rows);
+
+        Universe {
+            galaxies: self
+                .galaxies
+                .iter()
+                .map(|(x, y)| (xs[*x], ys[*y]))
+                .collect(),
+        }
+    }
+
+    /// Sum of the Manhattan distances between all pairs of galaxies after expansion.
+    pub fn sum_of_distances<T: PrimInt + FromPrimitive>(&self, factor: T) -> T {
+        let expansion = Expansion::Constant(factor);
+        self.expand(&expansion, &expansion).sum_of_distances()
+    }
+}
+
+/// An empty row or column, as seen by [`Expansion::Custom`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyLine {
+    /// Index of the line in the image
+    pub index: usize,
+    /// Number of empty lines before this one
+    pub ordinal: usize,
+    /// Length of the run of adjacent empty lines this line belongs to
+    pub run: usize,
+}
+
+/// Number of lines an empty row or column grows to.
+pub enum Expansion<T> {
+    Constant(T),
+    /// Size of every line by its index in the image, entries of non-empty lines are ignored
+    Table(Vec<T>),
+    Custom(Box<dyn Fn(EmptyLine) -> T>),
+}
+
+impl<T: Copy> Expansion<T> {
+    pub fn custom(f: impl Fn(EmptyLine) -> T + 'static) -> Self {
+        Self::Custom(Box::new(f))
+    }
+
+    pub fn size(&self, line: EmptyLine) -> T {
+        match self {
+            Expansion::Constant(factor) => *factor,
+            Expansion::Table(sizes) => sizes[line.index],
+            Expansion::Custom(f) => f(line),
+        }
+    }
+}
+
+/// Start coordinate of every line after expansion.
+fn line_offsets<T: PrimInt>(occupied: &[bool], expansion: &Expansion<T>) -> Vec<T> {
+    let mut runs = vec![0; occupied.len()];
+    let mut start = 0;
+    for i in 0..=occupied.len() {
+        if i == occupied.len() || occupied[i] {
+            runs[start..i].fill(i - start);
+            start = i + 1;
+        }
+    }
+
+    let mut offset = T::zero();
+    let mut ordinal = 0;
+    occupied
+        .iter()
+        .enumerate()
+        .map(|(index, o)| {
+            let current = offset;
+            offset = offset
+                + if *o {
+                    T::one()
+                } else {
+                    ordinal += 1;
+                    expansion.size(EmptyLine {
+                        index,
+                        ordinal: ordinal - 1,
+                        run: runs[index],
+                    })
+                };
+            current
+        })
+        .collect()
+}
+
+/// Expanded galaxies, named `1, 2, 3, ...` in reading order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Universe<T> {
+    pub galaxies: Vec<(T, T)>,
+}
+
+impl<T: PrimInt + FromPrimitive> Universe<T> {
+    fn position(&self, galaxy: usize) -> Option<(T, T)> {
+        galaxy
+            .checked_sub(1)
+            .and_then(|i| self.galaxies.get(i))
+            .copied()
+    }
+
+    pub fn distance(&self, a: usize, b: usize) -> Option<T> {
+        Some(manhattan(self.position(a)?, self.position(b)?))
+    }
+
+    /// Closest other galaxy and its distance, the lowest name winning ties.
+    pub fn nearest(&self, galaxy: usize) -> Option<(usize, T)> {
+        self.neighbours(galaxy)?.min_by_key(|(n, d)| (*d, *n))
+    }
+
+    /// Most distant other galaxy and its distance, the lowest name winning ties.
+    pub fn farthest(&self, galaxy: usize) -> Option<(usize, T)> {
+        self.neighbours(galaxy)?
+            .max_by_key(|(n, d)| (*d, Reverse(*n)))
+    }
+
+    fn neighbours(&self, galaxy: usize) -> Option<impl Iterator<Item = (usize, T)> + '_> {
+        let position = self.position(galaxy)?;
+        Some(
+            (1..=self.galaxies.len())
+                .filter(move |n| *n != galaxy)
+                .map(move |n| (n, manhattan(position, self.galaxies[n - 1]))),
+        )
+    }
+
+    /// Number of galaxy pairs per distance.
+    pub fn histogram(&self) -> BTreeMap<T, usize> {
+        let mut histogram = BTreeMap::new();
+        self.galaxies
+            .iter()
+            .tuple_combinations()
+            .for_each(|(a, b)| *histogram.entry(manhattan(*a, *b)).or_insert(0) += 1);
+        histogram
+    }
+
+    pub fn sum_of_distances(&self) -> T {
+        let (xs, ys) = self.galaxies.iter().copied().unzip();
+        sum_of_pairwise_differences(xs) + sum_of_pairwise_differences(ys)
+    }
+}
+
+fn manhattan<T: PrimInt>((x1, y1): (T, T), (x2, y2): (T, T)) -> T {
+    x1.max(x2) - x1.min(x2) + y1.max(y2) - y1.min(y2)
+}
+
+/// Sum of `|a - b|` over all pairs, in O(n log n) by sorting first.
+fn sum_of_pairwise_differences<T: PrimInt + FromPrimitive>(mut values: Vec<T>) -> T {
+    values.sort_unstable();
+    let mut preceding = T::zero();
+    let mut sum = T::zero();
+    for (i, v) in values.into_iter().enumerate() {
+        sum = sum + v * T::from_usize(i).expect("too many galaxies") - preceding;
+        preceding = preceding + v;
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expanded() {
+        let image = Image::parse("#..\n...\n..#");
+
+        assert_eq!(image.expanded(2u64), vec![(0, 0), (3, 3)]);
+        assert_eq!(
+            image.expanded(1_000_000u128),
+            vec![(0, 0), (1_000_001, 1_000_001)]
+        );
+        assert_eq!(image.sum_of_distances(10u64), 22);
+        assert_eq!(sum_of_pairwise_differences(vec![5u64, 1, 3]), 8);
+    }
+
+    #[test]
+    fn test_universe() {
+        let image = Image::parse("#....\n.....\n..#..\n.....\n.....\n....#");
+
+        let table = image.expand(
+            &Expansion::Table(vec![1u64, 7, 1, 3, 1]),
+            &Expansion::Constant(1),
+        );
+        assert_eq!(table.galaxies, vec![(0, 0), (8, 2), (12, 5)]);
+
+        let by_run = Expansion::custom(|line| 10u64.pow(line.run as u32));
+        let universe = image.expand(&by_run, &by_run);
+        assert_eq!(universe.galaxies, vec![(0, 0), (11, 11), (22, 212)]);
+        assert_eq!(universe.distance(1, 3), Some(234));
+        assert_eq!(universe.distance(1, 4), None);
+        assert_eq!(universe.nearest(1), Some((2, 22)));
+        assert_eq!(universe.farthest(2), Some((3, 212)));
+        assert_eq!(
+            universe.histogram(),
+            BTreeMap::from([(22, 1), (212, 1), (234, 1)])
+        );
+        assert_eq!(universe.sum_of_distances(), 468);
+    }
+}
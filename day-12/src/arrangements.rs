@@ -0,0 +1,159 @@
+use crate::{Record, SpringStatus};
+use rand::Rng;
+
+impl Record {
+    /// All valid arrangements in lexicographic order of their text, `#` before `.`.
+    pub fn arrangements(&self) -> Arrangements<'_> {
+        let table = self.table::<u128>();
+        Arrangements {
+            record: self,
+            runs: self.runs(),
+            next: 0,
+            total: table[0][0],
+            table,
+        }
+    }
+
+    /// Arrangement at `rank` in the lexicographic order.
+    pub fn nth_arrangement(&self, rank: u128) -> Option<Vec<SpringStatus>> {
+        self.arrangements().get(rank)
+    }
+
+    /// Uniformly random valid arrangement, if there is any.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Vec<SpringStatus>> {
+        let arrangements = self.arrangements();
+        if arrangements.total == 0 {
+            return None;
+        }
+        arrangements.get(rng.gen_range(0..arrangements.total))
+    }
+}
+
+/// Lazy iterator over the arrangements of a [`Record`], built on its counting table.
+#[derive(Debug, Clone)]
+pub struct Arrangements<'a> {
+    record: &'a Record,
+    table: Vec<Vec<u128>>,
+    runs: Vec<usize>,
+    next: u128,
+    total: u128,
+}
+
+impl Arrangements<'_> {
+    pub fn total(&self) -> u128 {
+        self.total
+    }
+
+    /// Arrangement at `rank`, skipping every subtree of the table the rank is not in.
+    pub fn get(&self, mut rank: u128) -> Option<Vec<SpringStatus>> {
+        if rank >= self.total {
+            return None;
+        }
+
+        let springs = &self.record.springs;
+        let groups = &self.record.groups;
+        let mut arrangement = Vec::with_capacity(springs.len());
+        let mut group = 0;
+        let mut pos = 0;
+
+        while pos < springs.len() {
+            let size = groups.get(group).copied();
+            let placed = match size {
+                Some(size) if self.record.fits(&self.runs, pos, size) => {
+                    self.table[group + 1][self.record.after(pos, size)]
+                }
+                _ => 0,
+            };
+
+            if rank < placed {
+                let size = size.expect("placed a group beyond the last one");
+                arrangement.extend(std::iter::repeat_n(SpringStatus::Damaged, size));
+                if pos + size < springs.len() {
+                    arrangement.push(SpringStatus::Operational);
+                }
+                pos = self.record.after(pos, size);
+                group += 1;
+            } else {
+                rank -= placed;
+                arrangement.push(SpringStatus::Operational);
+                pos += 1;
+            }
+        }
+
+        Some(arrangement)
+    }
+}
+
+impl Iterator for Arrangements<'_> {
+    type Item = Vec<SpringStatus>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let arrangement = self.get(self.next)?;
+        self.next += 1;
+        Some(arrangement)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.next = self.next.saturating_add(n as u128);
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.total.saturating_sub(self.next);
+        let lower = usize::try_from(remaining).unwrap_or(usize::MAX);
+        (lower, usize::try_from(remaining).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn text(arrangement: &[SpringStatus]) -> String {
+        arrangement.iter().map(|s| char::from(*s)).collect()
+    }
+
+    fn brute_force(record: &Record) -> Vec<String> {
+        record
+            .springs
+            .iter()
+            .map(|s| match s {
+                SpringStatus::Unknown => vec!['#', '.'],
+                s => vec![char::from(*s)],
+            })
+            .multi_cartesian_product()
+            .map(String::from_iter)
+            .filter(|s| {
+                s.split('.')
+                    .filter(|g| !g.is_empty())
+                    .map(str::len)
+                    .eq(record.groups.iter().copied())
+            })
+            .sorted()
+            .collect()
+    }
+
+    #[test]
+    fn test_arrangements() {
+        let record = Record::parse("?###???????? 3,2,1");
+        let all = record.arrangements().map(|a| text(&a)).collect::<Vec<_>>();
+
+        assert_eq!(all.len(), 10);
+        assert_eq!(all[0], ".###.##.#...");
+        assert_eq!(all[9], ".###....##.#");
+
+        assert_eq!(all, brute_force(&record));
+        assert_eq!(
+            record.nth_arrangement(3).map(|a| text(&a)).as_ref(),
+            Some(&all[3])
+        );
+        assert_eq!(record.nth_arrangement(10), None);
+
+        let mut rng = StdRng::seed_from_u64(12);
+        let sampled = record.sample(&mut rng).map(|a| text(&a)).unwrap();
+        assert!(all.contains(&sampled));
+        assert_eq!(Record::parse("#.? 2").sample(&mut rng), None);
+    }
+}
@@ -0,0 +1,162 @@
+use nom::{branch::alt, character::complete, combinator::value, multi::many1};
+use num::{One, Zero};
+use rayon::prelude::*;
+use std::ops::Add;
+
+mod arrangements;
+mod nonogram;
+pub use arrangements::Arrangements;
+pub use nonogram::{Contradiction, Grid, Line, Nonogram, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpringStatus {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl From<SpringStatus> for char {
+    fn from(value: SpringStatus) -> Self {
+        match value {
+            SpringStatus::Operational => '.',
+            SpringStatus::Damaged => '#',
+            SpringStatus::Unknown => '?',
+        }
+    }
+}
+
+/// One row of the condition records: the springs and the sizes of the damaged groups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub springs: Vec<SpringStatus>,
+    pub groups: Vec<usize>,
+}
+
+impl Record {
+    pub fn parse(line: &str) -> Self {
+        let (springs_str, group_size_str) = line.split_once(' ').expect("Invalid input");
+
+        let groups = group_size_str
+            .split(',')
+            .map(|s| s.parse::<usize>().expect("invalid char as number"))
+            .collect::<Vec<_>>();
+
+        let springs = many1(alt((
+            value(
+                SpringStatus::Operational,
+                complete::char::<&str, nom::error::Error<&str>>('.'),
+            ),
+            value(
+                SpringStatus::Damaged,
+                complete::char::<&str, nom::error::Error<&str>>('#'),
+            ),
+            value(
+                SpringStatus::Unknown,
+                complete::char::<&str, nom::error::Error<&str>>('?'),
+            ),
+        )))(springs_str)
+        .expect("Invalid input")
+        .1;
+
+        Self { springs, groups }
+    }
+
+    /// Repeats the springs `factor` times joined by unknown springs, and the groups `factor` times.
+    pub fn unfold(&self, factor: usize) -> Self {
+        let springs = (0..factor)
+            .map(|_| self.springs.as_slice())
+            .collect::<Vec<_>>()
+            .join(&SpringStatus::Unknown);
+
+        Self {
+            springs,
+            groups: self.groups.repeat(factor),
+        }
+    }
+
+    /// Number of damaged-or-unknown springs in a row starting at every position.
+    pub(crate) fn runs(&self) -> Vec<usize> {
+        let mut runs = vec![0; self.springs.len() + 1];
+        for i in (0..self.springs.len()).rev() {
+            if self.springs[i] != SpringStatus::Operational {
+                runs[i] = runs[i + 1] + 1;
+            }
+        }
+        runs
+    }
+
+    /// Whether a group of `size` damaged springs can start at `pos`.
+    pub(crate) fn fits(&self, runs: &[usize], pos: usize, size: usize) -> bool {
+        runs[pos] >= size && self.springs.get(pos + size) != Some(&SpringStatus::Damaged)
+    }
+
+    /// Position after a group of `size` starting at `pos` and the spring separating it.
+    pub(crate) fn after(&self, pos: usize, size: usize) -> usize {
+        (pos + size + 1).min(self.springs.len())
+    }
+
+    /// `table[g][pos]` is the number of ways to place the groups from `g` on in the springs from `pos`.
+    pub(crate) fn table<T: Clone + Zero + One + Add<Output = T>>(&self) -> Vec<Vec<T>> {
+        let len = self.springs.len();
+        let runs = self.runs();
+        let mut table = vec![vec![T::zero(); len + 1]; self.groups.len() + 1];
+
+        table[self.groups.len()][len] = T::one();
+        for pos in (0..len).rev() {
+            if self.springs[pos] != SpringStatus::Damaged {
+                table[self.groups.len()][pos] = table[self.groups.len()][pos + 1].clone();
+            }
+        }
+
+        for (g, &size) in self.groups.iter().enumerate().rev() {
+            for pos in (0..len).rev() {
+                let mut ways = T::zero();
+                if self.springs[pos] != SpringStatus::Damaged {
+                    ways = ways + table[g][pos + 1].clone();
+                }
+                if self.fits(&runs, pos, size) {
+                    ways = ways + table[g + 1][self.after(pos, size)].clone();
+                }
+                table[g][pos] = ways;
+            }
+        }
+
+        table
+    }
+
+    /// Number of arrangements of the springs matching the groups.
+    pub fn count<T: Clone + Zero + One + Add<Output = T>>(&self) -> T {
+        self.table::<T>().swap_remove(0).swap_remove(0)
+    }
+}
+
+/// Sum of the arrangements of every row after unfolding it `factor` times, rows solved in parallel.
+pub fn total_arrangements<T>(input: &str, factor: usize) -> T
+where
+    T: Clone + Zero + One + Add<Output = T> + Send,
+{
+    input
+        .par_lines()
+        .map(|l| Record::parse(l).unfold(factor).count::<T>())
+        .reduce(T::zero, |a, b| a + b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigUint;
+
+    #[test]
+    fn test_count() {
+        let record = Record::parse("?#?? 2");
+        assert_eq!(record.count::<u128>(), 2);
+        assert_eq!(record.unfold(2).springs.len(), 9);
+        assert_eq!(record.unfold(2).count::<u128>(), 4);
+
+        let unfolded = Record::parse("?###???????? 3,2,1").unfold(20);
+        assert_eq!(
+            unfolded.count::<BigUint>(),
+            BigUint::from(unfolded.count::<u128>())
+        );
+    }
+}
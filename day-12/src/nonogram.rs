@@ -0,0 +1,341 @@
+use crate::{Record, SpringStatus};
+use num::{One, Zero};
+use std::{
+    fmt,
+    ops::{Add, Mul},
+};
+
+/// Row and column clues of a paint-by-numbers puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nonogram {
+    pub rows: Vec<Vec<usize>>,
+    pub columns: Vec<Vec<usize>>,
+}
+
+/// Cells of a (partially) solved puzzle: damaged is filled, operational is blank.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid {
+    pub cells: Vec<Vec<SpringStatus>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line {
+    Row(usize),
+    Column(usize),
+}
+
+/// A line whose clue no arrangement of its known cells can satisfy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contradiction(pub Line);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solution {
+    Unique(Grid),
+    /// The first two solutions found, the search stops there
+    Multiple(Vec<Grid>),
+    Contradiction,
+}
+
+impl Nonogram {
+    /// Parses the row clues and the column clues as two blocks separated by an empty line.
+    /// Every line holds one clue like `1,1,3`, with `0` for a blank line.
+    pub fn parse(input: &str) -> Self {
+        let (rows, columns) = input
+            .trim()
+            .split_once("\n\n")
+            .or_else(|| input.trim().split_once("\r\n\r\n"))
+            .expect("missing column clues");
+
+        let clues = |block: &str| {
+            block
+                .lines()
+                .map(|l| {
+                    l.split(',')
+                        .map(|s| s.trim().parse::<usize>().expect("invalid clue"))
+                        .filter(|g| *g != 0)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        Self {
+            rows: clues(rows),
+            columns: clues(columns),
+        }
+    }
+
+    pub fn empty_grid(&self) -> Grid {
+        Grid {
+            cells: vec![vec![SpringStatus::Unknown; self.columns.len()]; self.rows.len()],
+        }
+    }
+
+    /// Fills every cell forced by its row or column until nothing changes.
+    pub fn propagate(&self, grid: &mut Grid) -> Result<(), Contradiction> {
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for (y, clue) in self.rows.iter().enumerate() {
+                let solved = solve_line(&grid.cells[y], clue).ok_or(Contradiction(Line::Row(y)))?;
+                if solved != grid.cells[y] {
+                    grid.cells[y] = solved;
+                    changed = true;
+                }
+            }
+
+            for (x, clue) in self.columns.iter().enumerate() {
+                let column = grid.cells.iter().map(|row| row[x]).collect::<Vec<_>>();
+                let solved = solve_line(&column, clue).ok_or(Contradiction(Line::Column(x)))?;
+                if solved != column {
+                    grid.cells
+                        .iter_mut()
+                        .zip(solved)
+                        .for_each(|(row, cell)| row[x] = cell);
+                    changed = true;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Every solution, found by propagation and guessing the first unknown cell where it stalls.
+    pub fn solutions(&self) -> Vec<Grid> {
+        let mut solutions = Vec::new();
+        self.search(self.empty_grid(), &mut solutions, usize::MAX);
+        solutions
+    }
+
+    /// Collects solutions until there are `limit` of them.
+    fn search(&self, mut grid: Grid, solutions: &mut Vec<Grid>, limit: usize) {
+        if solutions.len() >= limit || self.propagate(&mut grid).is_err() {
+            return;
+        }
+
+        match grid.first_unknown() {
+            None => solutions.push(grid),
+            Some((x, y)) => {
+                for guess in [SpringStatus::Damaged, SpringStatus::Operational] {
+                    let mut guessed = grid.clone();
+                    guessed.cells[y][x] = guess;
+                    self.search(guessed, solutions, limit);
+                }
+            }
+        }
+    }
+
+    /// Decides between a unique solution, several and none, stopping at the second solution.
+    pub fn solve(&self) -> Solution {
+        let mut solutions = Vec::new();
+        self.search(self.empty_grid(), &mut solutions, 2);
+        match solutions.len() {
+            0 => Solution::Contradiction,
+            1 => Solution::Unique(solutions.remove(0)),
+            _ => Solution::Multiple(solutions),
+        }
+    }
+
+    /// The grid with the row clues next to every row.
+    pub fn render(&self, grid: &Grid) -> String {
+        grid.to_string()
+            .lines()
+            .zip(&self.rows)
+            .map(|(line, clue)| {
+                let clue = clue.iter().map(usize::to_string).collect::<Vec<_>>();
+                let clue = if clue.is_empty() {
+                    "0".to_string()
+                } else {
+                    clue.join(",")
+                };
+                format!("{} {}\n", line, clue)
+            })
+            .collect()
+    }
+}
+
+impl Grid {
+    pub fn first_unknown(&self) -> Option<(usize, usize)> {
+        self.cells.iter().enumerate().find_map(|(y, row)| {
+            row.iter()
+                .position(|c| *c == SpringStatus::Unknown)
+                .map(|x| (x, y))
+        })
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.first_unknown().is_none()
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.cells.iter().try_for_each(|row| {
+            writeln!(
+                f,
+                "{}",
+                row.iter().map(|c| char::from(*c)).collect::<String>()
+            )
+        })
+    }
+}
+
+/// Whether any arrangement exists, as a semiring so the counting table can be reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Feasible(bool);
+
+impl Add for Feasible {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Feasible(self.0 || rhs.0)
+    }
+}
+
+impl Mul for Feasible {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Feasible(self.0 && rhs.0)
+    }
+}
+
+impl Zero for Feasible {
+    fn zero() -> Self {
+        Feasible(false)
+    }
+
+    fn is_zero(&self) -> bool {
+        !self.0
+    }
+}
+
+impl One for Feasible {
+    fn one() -> Self {
+        Feasible(true)
+    }
+}
+
+/// Known cells of the line after the clue, or `None` if the clue cannot be met.
+///
+/// The table of the record says which states can still finish the line. Walking forward
+/// through the states reachable from the start, every step that can also finish marks
+/// the cells it sets as possible. A cell is forced if only one value is possible.
+fn solve_line(cells: &[SpringStatus], clue: &[usize]) -> Option<Vec<SpringStatus>> {
+    let record = Record {
+        springs: cells.to_vec(),
+        groups: clue.to_vec(),
+    };
+    let can_finish = record.table::<Feasible>();
+    if !can_finish[0][0].0 {
+        return None;
+    }
+
+    let len = cells.len();
+    let runs = record.runs();
+    let mut reached = vec![vec![false; len + 1]; clue.len() + 1];
+    reached[0][0] = true;
+    let mut operational = vec![false; len];
+    // +1 where a possible group starts, -1 after it ends
+    let mut damaged = vec![0i64; len + 1];
+
+    for pos in 0..len {
+        for g in 0..=clue.len() {
+            if !reached[g][pos] {
+                continue;
+            }
+            if cells[pos] != SpringStatus::Damaged && can_finish[g][pos + 1].0 {
+                reached[g][pos + 1] = true;
+                operational[pos] = true;
+            }
+            if let Some(&size) = clue.get(g) {
+                let next = record.after(pos, size);
+                if record.fits(&runs, pos, size) && can_finish[g + 1][next].0 {
+                    reached[g + 1][next] = true;
+                    damaged[pos] += 1;
+                    damaged[pos + size] -= 1;
+                    if pos + size < len {
+                        operational[pos + size] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut covered = 0;
+    Some(
+        cells
+            .iter()
+            .zip(operational)
+            .enumerate()
+            .map(|(i, (cell, operational))| {
+                covered += damaged[i];
+                match (covered > 0, operational) {
+                    (true, false) => SpringStatus::Damaged,
+                    (false, true) => SpringStatus::Operational,
+                    _ => *cell,
+                }
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_nonogram() {
+        let heart = Nonogram::parse("1,1\n5\n5\n3\n1\n\n2\n4\n4\n4\n2");
+        let mut grid = heart.empty_grid();
+        heart.propagate(&mut grid).unwrap();
+        assert!(grid.is_solved());
+        assert_eq!(grid.to_string(), ".#.#.\n#####\n#####\n.###.\n..#..\n");
+        assert_eq!(heart.solve(), Solution::Unique(grid));
+
+        let diagonal = Nonogram::parse("1\n1\n\n1\n1");
+        let mut grid = diagonal.empty_grid();
+        diagonal.propagate(&mut grid).unwrap();
+        assert_eq!(diagonal.render(&grid), "?? 1\n?? 1\n");
+        assert!(matches!(diagonal.solve(), Solution::Multiple(s) if s.len() == 2));
+
+        let impossible = Nonogram::parse("3\n0\n\n1\n0");
+        assert_eq!(
+            impossible.propagate(&mut impossible.empty_grid()),
+            Err(Contradiction(Line::Row(0)))
+        );
+        assert_eq!(impossible.solve(), Solution::Contradiction);
+
+        let permutations = Nonogram::parse(&format!("{0}\n{0}", "1\n".repeat(10)));
+        assert!(matches!(permutations.solve(), Solution::Multiple(s) if s.len() == 2));
+    }
+
+    #[test]
+    fn test_solve_line() {
+        use SpringStatus::*;
+
+        for line in [
+            "??#???#??? 3,2",
+            "?.??#?.??? 1,2,1",
+            "#????????? 1,1,1",
+            "??#?? 5",
+        ] {
+            let line = Record::parse(line);
+            let arrangements = line.arrangements().collect::<Vec<_>>();
+            let solved = solve_line(&line.springs, &line.groups).unwrap();
+            for (i, cell) in solved.iter().enumerate() {
+                let values = arrangements.iter().map(|a| a[i]).collect::<HashSet<_>>();
+                match values.len() {
+                    1 => assert!(values.contains(cell)),
+                    _ => assert_eq!(*cell, line.springs[i]),
+                }
+            }
+        }
+        assert_eq!(
+            solve_line(&[Unknown; 4], &[3]),
+            Some(vec![Unknown, Damaged, Damaged, Unknown])
+        );
+        assert_eq!(solve_line(&[Damaged, Damaged], &[1]), None);
+    }
+}
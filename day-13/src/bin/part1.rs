@@ -1,35 +1,21 @@
-fn main() {
-    println!("{}", part1(include_str!("./input.txt")));
-}
-
-fn part1(input: &str) -> usize {
-    input.split("\n\n").map(process_pattern).sum()
-}
-
-fn process_pattern(pattern: &str) -> usize {
-    let lines = pattern
-        .lines()
-        .map(|s| s.chars().collect())
-        .collect::<Vec<Vec<_>>>();
-    let mut sum = check_for_reflection(lines.clone()) * 100;
-    let width = lines[0].len();
-    let columns = (0..width)
-        .map(|i| lines.iter().map(|row| row[i]).collect::<Vec<char>>())
-        .collect();
-    sum += check_for_reflection(columns);
+use day_13::{report, summarize, InputError};
 
-    sum
+fn main() {
+    let input = include_str!("./input.txt");
+    if std::env::args().any(|a| a == "--render") {
+        print!("{}", report(input, 0));
+    }
+    match part1(input) {
+        Ok(sum) => println!("{}", sum),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
 }
 
-fn check_for_reflection(pattern: Vec<Vec<char>>) -> usize {
-    (1..pattern.len())
-        .find(|i| {
-            let a = &pattern[0..*i];
-            let b = &pattern[*i..];
-
-            a.iter().rev().zip(b).all(|(a, b)| a == b)
-        })
-        .unwrap_or(0)
+fn part1(input: &str) -> Result<usize, InputError> {
+    summarize(input, 0)
 }
 
 #[cfg(test)]
@@ -59,7 +45,7 @@ mod tests {
                 #....#..#
                 "
             )),
-            405
+            Ok(405)
         );
     }
 }
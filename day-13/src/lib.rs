@@ -0,0 +1,421 @@
+use std::{collections::HashSet, error::Error, fmt};
+
+/// Bits of a row or column, 64 cells per word.
+pub type Mask = Vec<u64>;
+
+/// A pattern of ash and rocks with every row and column stored as a bitmask of its rocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub rows: Vec<Mask>,
+    pub columns: Vec<Mask>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    InvalidCharacter(char),
+    /// A row whose length differs from the first row
+    Ragged {
+        row: usize,
+    },
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::InvalidCharacter(c) => write!(f, "invalid character: {}", c),
+            PatternError::Ragged { row } => {
+                write!(f, "row {} differs in length from the first row", row + 1)
+            }
+        }
+    }
+}
+
+impl Error for PatternError {}
+
+impl Pattern {
+    /// Parses a pattern of `.` and `#` with rows of equal length.
+    pub fn parse(pattern: &str) -> Result<Self, PatternError> {
+        let lines = pattern.lines().collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |l| l.len());
+        let height = lines.len();
+
+        let mut rows = vec![vec![0; width.div_ceil(64)]; height];
+        let mut columns = vec![vec![0; height.div_ceil(64)]; width];
+        for (y, line) in lines.iter().enumerate() {
+            if line.len() != width {
+                return Err(PatternError::Ragged { row: y });
+            }
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    '#' => {
+                        set(&mut rows[y], x);
+                        set(&mut columns[x], y);
+                    }
+                    '.' => {}
+                    _ => return Err(PatternError::InvalidCharacter(c)),
+                }
+            }
+        }
+
+        Ok(Self { rows, columns })
+    }
+
+    /// Number of rows above the horizontal mirror with exactly `smudges` differing cells.
+    pub fn horizontal_reflection(&self, smudges: u32) -> Option<usize> {
+        find_reflection(&self.rows, smudges)
+    }
+
+    /// Number of columns left of the vertical mirror with exactly `smudges` differing cells.
+    pub fn vertical_reflection(&self, smudges: u32) -> Option<usize> {
+        find_reflection(&self.columns, smudges)
+    }
+
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_rock(&self, x: usize, y: usize) -> bool {
+        get(&self.rows[y], x)
+    }
+
+    /// Every mirror with exactly `smudges` differing cells, horizontal ones first.
+    pub fn reflections(&self, smudges: u32) -> Vec<Reflection> {
+        let horizontal = (1..self.height())
+            .filter(|axis| mismatches(&self.rows, *axis) == smudges)
+            .map(|axis| self.reflection_at(Orientation::Horizontal, axis));
+        let vertical = (1..self.width())
+            .filter(|axis| mismatches(&self.columns, *axis) == smudges)
+            .map(|axis| self.reflection_at(Orientation::Vertical, axis));
+        horizontal.chain(vertical).collect()
+    }
+
+    /// The single mirror with exactly `smudges` differing cells.
+    pub fn reflection(&self, smudges: u32) -> Result<Reflection, ReflectionError> {
+        let mut reflections = self.reflections(smudges);
+        match reflections.len() {
+            0 => Err(ReflectionError::NoReflection),
+            1 => Ok(reflections.remove(0)),
+            _ => Err(ReflectionError::Ambiguous(reflections)),
+        }
+    }
+
+    fn reflection_at(&self, orientation: Orientation, axis: usize) -> Reflection {
+        let lines = match orientation {
+            Orientation::Horizontal => &self.rows,
+            Orientation::Vertical => &self.columns,
+        };
+        let span = axis.min(lines.len() - axis);
+
+        let smudges = (0..span)
+            .flat_map(|i| {
+                let (before, after) = (axis - 1 - i, axis + i);
+                differing(&lines[before], &lines[after]).map(move |bit| match orientation {
+                    Orientation::Horizontal => Smudge {
+                        cell: (bit, before),
+                        mirrored: (bit, after),
+                    },
+                    Orientation::Vertical => Smudge {
+                        cell: (before, bit),
+                        mirrored: (after, bit),
+                    },
+                })
+            })
+            .collect();
+
+        Reflection {
+            orientation,
+            axis,
+            span,
+            smudges,
+        }
+    }
+
+    /// The pattern with the mirror line drawn in and smudged cells highlighted in red.
+    pub fn render(&self, reflection: &Reflection) -> String {
+        let smudged = reflection
+            .smudges
+            .iter()
+            .flat_map(|s| [s.cell, s.mirrored])
+            .collect::<HashSet<_>>();
+
+        let mut out = String::new();
+        for y in 0..self.height() {
+            if reflection.orientation == Orientation::Horizontal && y == reflection.axis {
+                out.push_str(&format!(
+                    "{}{}{}\n",
+                    MIRROR,
+                    "─".repeat(self.width()),
+                    RESET
+                ));
+            }
+            for x in 0..self.width() {
+                if reflection.orientation == Orientation::Vertical && x == reflection.axis {
+                    out.push_str(&format!("{}│{}", MIRROR, RESET));
+                }
+                let c = if self.is_rock(x, y) { '#' } else { '.' };
+                if smudged.contains(&(x, y)) {
+                    out.push_str(&format!("{}{}{}", SMUDGE, c, RESET));
+                } else {
+                    out.push(c);
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+const MIRROR: &str = "\x1b[1;36m";
+const SMUDGE: &str = "\x1b[1;41m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Mirror between two rows
+    Horizontal,
+    /// Mirror between two columns
+    Vertical,
+}
+
+/// A pair of mirrored cells that differ, as `(x, y)` positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Smudge {
+    pub cell: (usize, usize),
+    pub mirrored: (usize, usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reflection {
+    pub orientation: Orientation,
+    /// Number of rows above or columns left of the mirror
+    pub axis: usize,
+    /// Number of lines mirrored on each side before hitting the edge
+    pub span: usize,
+    pub smudges: Vec<Smudge>,
+}
+
+impl Reflection {
+    /// 100 times the rows above a horizontal mirror, or the columns left of a vertical one.
+    pub fn summary(&self) -> usize {
+        match self.orientation {
+            Orientation::Horizontal => self.axis * 100,
+            Orientation::Vertical => self.axis,
+        }
+    }
+}
+
+impl fmt::Display for Reflection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} mirror after {}, spanning {}",
+            self.orientation, self.axis, self.span
+        )?;
+        self.smudges
+            .iter()
+            .try_for_each(|s| write!(f, ", smudge at {:?} mirroring {:?}", s.cell, s.mirrored))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReflectionError {
+    NoReflection,
+    Ambiguous(Vec<Reflection>),
+}
+
+impl fmt::Display for ReflectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReflectionError::NoReflection => write!(f, "no reflection found"),
+            ReflectionError::Ambiguous(candidates) => {
+                write!(f, "{} candidate reflections", candidates.len())?;
+                candidates.iter().try_for_each(|r| write!(f, "; {}", r))
+            }
+        }
+    }
+}
+
+impl Error for ReflectionError {}
+
+/// A pattern of the input that cannot be summarized, with its 1-based position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    Pattern(usize, PatternError),
+    Reflection(usize, ReflectionError),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Pattern(i, e) => write!(f, "pattern {}: {}", i, e),
+            InputError::Reflection(i, e) => write!(f, "pattern {}: {}", i, e),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+fn patterns(input: &str) -> impl Iterator<Item = (usize, Result<Pattern, PatternError>)> + '_ {
+    input
+        .split("\n\n")
+        .enumerate()
+        .map(|(i, p)| (i + 1, Pattern::parse(p)))
+}
+
+/// Sum of the summaries of every pattern's single reflection with exactly `smudges` differing cells.
+pub fn summarize(input: &str, smudges: u32) -> Result<usize, InputError> {
+    patterns(input)
+        .map(|(i, pattern)| {
+            pattern
+                .map_err(|e| InputError::Pattern(i, e))?
+                .reflection(smudges)
+                .map(|r| r.summary())
+                .map_err(|e| InputError::Reflection(i, e))
+        })
+        .sum()
+}
+
+/// Every pattern rendered with its reflection, or why it has none.
+pub fn report(input: &str, smudges: u32) -> String {
+    patterns(input)
+        .map(
+            |(i, pattern)| match pattern.map(|p| (p.reflection(smudges), p)) {
+                Ok((Ok(reflection), pattern)) => {
+                    format!(
+                        "pattern {}: {}\n{}\n",
+                        i,
+                        reflection,
+                        pattern.render(&reflection)
+                    )
+                }
+                Ok((Err(e), _)) => format!("{}\n\n", InputError::Reflection(i, e)),
+                Err(e) => format!("{}\n\n", InputError::Pattern(i, e)),
+            },
+        )
+        .collect()
+}
+
+/// Number of differing cells when folding `lines` after the first `axis` lines.
+pub fn mismatches(lines: &[Mask], axis: usize) -> u32 {
+    lines[..axis]
+        .iter()
+        .rev()
+        .zip(&lines[axis..])
+        .map(|(a, b)| {
+            a.iter()
+                .zip(b)
+                .map(|(a, b)| (a ^ b).count_ones())
+                .sum::<u32>()
+        })
+        .sum()
+}
+
+fn find_reflection(lines: &[Mask], smudges: u32) -> Option<usize> {
+    (1..lines.len()).find(|axis| mismatches(lines, *axis) == smudges)
+}
+
+fn get(mask: &[u64], i: usize) -> bool {
+    mask[i / 64] & (1 << (i % 64)) != 0
+}
+
+fn set(mask: &mut [u64], i: usize) {
+    mask[i / 64] |= 1 << (i % 64);
+}
+
+/// Indices of the cells that differ between two masks.
+fn differing<'a>(a: &'a [u64], b: &'a [u64]) -> impl Iterator<Item = usize> + 'a {
+    a.iter().zip(b).enumerate().flat_map(|(word, (a, b))| {
+        let diff = a ^ b;
+        (0..64)
+            .filter(move |bit| diff & (1 << bit) != 0)
+            .map(move |bit| word * 64 + bit)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reflections() {
+        let pattern = Pattern::parse(
+            "#.##..##.\n..#.##.#.\n##......#\n##......#\n..#.##.#.\n..##..##.\n#.#.##.#.",
+        )
+        .unwrap();
+
+        assert_eq!(pattern.rows[0], vec![0b011001101]);
+        assert_eq!(pattern.vertical_reflection(0), Some(5));
+        assert_eq!(pattern.horizontal_reflection(1), Some(3));
+        assert_eq!(mismatches(&pattern.columns, 1), 2);
+
+        let rock = format!("{}#{}", ".".repeat(65), ".".repeat(4));
+        let wide = Pattern::parse(&format!("{0}\n{0}\n{1}", rock, ".".repeat(70))).unwrap();
+        assert_eq!(wide.horizontal_reflection(0), Some(1));
+        assert!(wide.is_rock(65, 1));
+        assert!(wide.reflections(1).iter().any(|r| {
+            r.orientation == Orientation::Horizontal && r.axis == 2 && r.smudges[0].cell == (65, 1)
+        }));
+
+        assert_eq!(
+            Pattern::parse("#.\n#"),
+            Err(PatternError::Ragged { row: 1 })
+        );
+        assert_eq!(
+            Pattern::parse("#x"),
+            Err(PatternError::InvalidCharacter('x'))
+        );
+    }
+
+    #[test]
+    fn test_reflection_report() {
+        let pattern = Pattern::parse(
+            "#.##..##.\n..#.##.#.\n##......#\n##......#\n..#.##.#.\n..##..##.\n#.#.##.#.",
+        )
+        .unwrap();
+
+        let smudged = pattern.reflection(1).unwrap();
+        assert_eq!(smudged.orientation, Orientation::Horizontal);
+        assert_eq!(smudged.summary(), 300);
+        assert_eq!(smudged.span, 3);
+        assert_eq!(
+            smudged.smudges,
+            vec![Smudge {
+                cell: (0, 0),
+                mirrored: (0, 5)
+            }]
+        );
+        let rendered = pattern.render(&smudged);
+        assert!(rendered.starts_with(&format!("{}#{}.##..##.\n", SMUDGE, RESET)));
+        assert_eq!(rendered.lines().count(), 8);
+
+        assert_eq!(pattern.reflection(0).map(|r| (r.axis, r.span)), Ok((5, 4)));
+        assert_eq!(pattern.reflection(40), Err(ReflectionError::NoReflection));
+        assert!(matches!(
+            Pattern::parse("..\n..").unwrap().reflection(0),
+            Err(ReflectionError::Ambiguous(r)) if r.len() == 2
+        ));
+    }
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(summarize("#.\n#.\n\n.#\n.#", 0), Ok(200));
+        assert_eq!(
+            summarize("#.\n#.\n\n##\n.#", 0),
+            Err(InputError::Reflection(2, ReflectionError::NoReflection))
+        );
+        assert_eq!(
+            summarize("#.\n#.\n\n#.\n#x", 0),
+            Err(InputError::Pattern(2, PatternError::InvalidCharacter('x')))
+        );
+
+        let input = "#.\n#.\n\n##\n.#\n\n#.\n#x";
+        let report = report(input, 0);
+        assert!(report.starts_with("pattern 1: Horizontal mirror after 1, spanning 1\n"));
+        assert!(report.contains("pattern 2: no reflection found\n"));
+        assert!(report.ends_with("pattern 3: invalid character: x\n\n"));
+    }
+}
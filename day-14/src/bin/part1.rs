@@ -1,61 +1,17 @@
-use std::cmp::Ordering;
+use day_14::{Platform, Tilt};
 
 fn main() {
     println!("{}", part1(include_str!("./input.txt")));
 }
 
 fn part1(input: &str) -> usize {
-    let slid_lines = slide_north(input);
-
-    slid_lines
-        .iter()
-        .enumerate()
-        .map(|(i, line)| line.iter().filter(|c| **c == 'O').count() * (slid_lines.len() - i))
-        .sum()
+    slide_north(input).north_load()
 }
 
-fn slide_north(input: &str) -> Vec<Vec<char>> {
-    let lines = input.lines().collect::<Vec<_>>();
-    let columns = (0..lines[0].len())
-        .map(|i| {
-            let mut column = lines
-                .iter()
-                .map(|row| row.chars().nth(i).unwrap())
-                .collect::<Vec<_>>();
-
-            // bubble sort
-            for n in (1..column.len()).rev() {
-                for j in 0..n {
-                    if sort_slide_north(&column[j], &column[j + 1]) == Ordering::Greater {
-                        column.swap(j, j + 1);
-                    }
-                }
-            }
-
-            column
-        })
-        .collect::<Vec<_>>();
-
-    (0..columns[0].len())
-        .map(|i| {
-            columns
-                .iter()
-                .map(|column| *column.get(i).unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>()
-}
-
-fn sort_slide_north(a: &char, b: &char) -> Ordering {
-    if a == &'#' || b == &'#' {
-        Ordering::Equal
-    } else if a == &'.' && b == &'O' {
-        Ordering::Greater
-    } else if a == &'O' && b == &'.' {
-        Ordering::Less
-    } else {
-        a.cmp(b)
-    }
+fn slide_north(input: &str) -> Platform {
+    let mut platform = Platform::parse(input);
+    platform.tilt(Tilt::North);
+    platform
 }
 
 #[cfg(test)]
@@ -86,11 +42,7 @@ mod tests {
     #[test]
     fn test_slide_north() {
         assert_eq!(
-            slide_north(INPUT)
-                .iter()
-                .map(|line| line.iter().collect::<String>())
-                .collect::<Vec<_>>()
-                .join("\n"),
+            slide_north(INPUT).to_string(),
             indoc!(
                 "
                 OOOO.#.O..
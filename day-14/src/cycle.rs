@@ -0,0 +1,84 @@
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hash},
+};
+
+/// States of a walk from a start state up to the first repeated one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    /// Steps before the first state that is part of the cycle
+    pub prefix: u64,
+    pub period: u64,
+    /// The first `prefix + period` states, the start state first
+    pub states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// State after `n` steps from the start state.
+    pub fn state_at(&self, n: impl Into<u128>) -> &S {
+        let n = n.into();
+        let prefix = self.prefix as u128;
+        let index = if n < prefix {
+            n
+        } else {
+            prefix + (n - prefix) % self.period as u128
+        };
+        &self.states[index as usize]
+    }
+}
+
+/// Applies `step` from `start` until a state repeats.
+///
+/// Every state is stored once in `states`, the lookup only maps hashes to indices into it.
+pub fn find_cycle<S, F>(start: S, mut step: F) -> Cycle<S>
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    let hasher = RandomState::new();
+    let mut seen = HashMap::<u64, Vec<usize>>::new();
+    let mut states = Vec::new();
+    let mut state = start;
+
+    loop {
+        let hash = hasher.hash_one(&state);
+        let indices = seen.entry(hash).or_default();
+        if let Some(&first) = indices.iter().find(|&&i| states[i] == state) {
+            return Cycle {
+                prefix: first as u64,
+                period: (states.len() - first) as u64,
+                states,
+            };
+        }
+        indices.push(states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// State after `n` steps, for any `n` once the cycle of the walk is known.
+pub fn state_after<S, F>(start: S, step: F, n: impl Into<u128>) -> S
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    find_cycle(start, step).state_at(n).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3
+        let step = |x: &u8| if *x == 6 { 3 } else { x + 1 };
+
+        let cycle = find_cycle(0, step);
+        assert_eq!((cycle.prefix, cycle.period), (3, 4));
+        assert_eq!(*cycle.state_at(2u64), 2);
+        assert_eq!(*cycle.state_at(7u64), 3);
+        assert_eq!(state_after(0, step, 10u128.pow(30) + 4), 4);
+    }
+}
@@ -0,0 +1,237 @@
+use std::fmt;
+
+mod cycle;
+pub use cycle::{find_cycle, state_after, Cycle};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tilt {
+    North,
+    West,
+    South,
+    East,
+}
+
+/// The platform with rounded rocks as a bitset over `y * width + x`.
+///
+/// Cube rocks never move, so the runs of cells between them are computed once per row
+/// and column and every tilt only counts and refills the rounded rocks of each run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Platform {
+    pub width: usize,
+    pub height: usize,
+    rounded: Vec<u64>,
+    cubes: Vec<u64>,
+    /// Runs of `(start, end)` x coordinates without cube rocks, per row
+    row_segments: Vec<Vec<(usize, usize)>>,
+    /// Runs of `(start, end)` y coordinates without cube rocks, per column
+    column_segments: Vec<Vec<(usize, usize)>>,
+}
+
+impl Platform {
+    pub fn parse(input: &str) -> Self {
+        let lines = input.lines().collect::<Vec<_>>();
+        let height = lines.len();
+        let width = lines.first().map_or(0, |l| l.len());
+        let words = (width * height).div_ceil(64);
+
+        let mut platform = Self {
+            width,
+            height,
+            rounded: vec![0; words],
+            cubes: vec![0; words],
+            row_segments: Vec::new(),
+            column_segments: Vec::new(),
+        };
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    'O' => set(&mut platform.rounded, y * width + x, true),
+                    '#' => set(&mut platform.cubes, y * width + x, true),
+                    '.' => {}
+                    _ => panic!("invalid char: {}", c),
+                }
+            }
+        }
+
+        platform.row_segments = (0..height)
+            .map(|y| segments(width, |x| platform.is_cube(x, y)))
+            .collect();
+        platform.column_segments = (0..width)
+            .map(|x| segments(height, |y| platform.is_cube(x, y)))
+            .collect();
+        platform
+    }
+
+    pub fn is_rounded(&self, x: usize, y: usize) -> bool {
+        get(&self.rounded, y * self.width + x)
+    }
+
+    pub fn is_cube(&self, x: usize, y: usize) -> bool {
+        get(&self.cubes, y * self.width + x)
+    }
+
+    /// Positions of the rounded rocks, the only part of the platform that changes.
+    pub fn rounded(&self) -> &[u64] {
+        &self.rounded
+    }
+
+    /// Slides every rounded rock as far as possible in one direction.
+    pub fn tilt(&mut self, tilt: Tilt) {
+        let width = self.width;
+        match tilt {
+            Tilt::North | Tilt::South => {
+                for (x, segments) in self.column_segments.iter().enumerate() {
+                    for &(start, end) in segments {
+                        fill_segment(&mut self.rounded, start, end, tilt == Tilt::North, |y| {
+                            y * width + x
+                        });
+                    }
+                }
+            }
+            Tilt::West | Tilt::East => {
+                for (y, segments) in self.row_segments.iter().enumerate() {
+                    for &(start, end) in segments {
+                        fill_segment(&mut self.rounded, start, end, tilt == Tilt::West, |x| {
+                            y * width + x
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Tilts north, west, south and east.
+    pub fn spin_cycle(&mut self) {
+        [Tilt::North, Tilt::West, Tilt::South, Tilt::East]
+            .into_iter()
+            .for_each(|t| self.tilt(t));
+    }
+
+    /// The platform after `n` spin cycles, skipping ahead once the positions repeat.
+    ///
+    /// Only the rounded rocks take part in the cycle detection, the cube rocks and
+    /// segments are shared by every state.
+    pub fn after_spin_cycles(&self, n: impl Into<u128>) -> Self {
+        let mut scratch = self.clone();
+        let rounded = state_after(
+            self.rounded.clone(),
+            |rounded| {
+                scratch.rounded.clone_from(rounded);
+                scratch.spin_cycle();
+                scratch.rounded.clone()
+            },
+            n,
+        );
+        Self {
+            rounded,
+            ..self.clone()
+        }
+    }
+
+    /// Sum over the rounded rocks of their distance to the south edge, counting their own row.
+    pub fn north_load(&self) -> usize {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|(x, y)| self.is_rounded(*x, *y))
+            .map(|(_, y)| self.height - y)
+            .sum()
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.width {
+                let c = if self.is_rounded(x, y) {
+                    'O'
+                } else if self.is_cube(x, y) {
+                    '#'
+                } else {
+                    '.'
+                };
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn get(bits: &[u64], i: usize) -> bool {
+    bits[i / 64] & (1 << (i % 64)) != 0
+}
+
+fn set(bits: &mut [u64], i: usize, value: bool) {
+    if value {
+        bits[i / 64] |= 1 << (i % 64);
+    } else {
+        bits[i / 64] &= !(1 << (i % 64));
+    }
+}
+
+fn segments(len: usize, is_cube: impl Fn(usize) -> bool) -> Vec<(usize, usize)> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for i in 0..=len {
+        if i == len || is_cube(i) {
+            if start < i {
+                segments.push((start, i));
+            }
+            start = i + 1;
+        }
+    }
+    segments
+}
+
+/// Moves the rounded rocks of the cells `start..end` to the start or the end of the run.
+fn fill_segment(
+    bits: &mut [u64],
+    start: usize,
+    end: usize,
+    to_start: bool,
+    index: impl Fn(usize) -> usize,
+) {
+    let count = (start..end).filter(|i| get(bits, index(*i))).count();
+    let boundary = if to_start { start + count } else { end - count };
+    for i in start..end {
+        set(bits, index(i), (i < boundary) == to_start);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tilt() {
+        let mut platform = Platform::parse("O.#O\n.O..\n#..O");
+
+        assert_eq!(platform.row_segments[0], vec![(0, 2), (3, 4)]);
+        assert_eq!(platform.column_segments[0], vec![(0, 2)]);
+
+        platform.tilt(Tilt::East);
+        assert_eq!(platform.to_string(), ".O#O\n...O\n#..O");
+        platform.tilt(Tilt::South);
+        assert_eq!(platform.to_string(), "..#O\n...O\n#O.O");
+        assert_eq!(platform.north_load(), 7);
+        platform.tilt(Tilt::West);
+        platform.tilt(Tilt::North);
+        assert_eq!(platform.to_string(), "OO#O\n..O.\n#...");
+    }
+
+    #[test]
+    fn test_after_spin_cycles() {
+        let platform = Platform::parse("O.#O\n.O..\n#..O");
+        let mut spun = platform.clone();
+        (0..5).for_each(|_| spun.spin_cycle());
+
+        assert_eq!(platform.after_spin_cycles(0u64), platform);
+        assert_eq!(platform.after_spin_cycles(5u64), spun);
+        assert_eq!(
+            platform.after_spin_cycles(u128::MAX).to_string(),
+            spun.to_string()
+        );
+    }
+}